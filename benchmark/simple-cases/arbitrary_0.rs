@@ -0,0 +1,25 @@
+// Arbitrary values take a valid variant of an enum, and a reference points to
+// a fresh object with an arbitrary value.
+#[derive(rbmc::Arbitrary)]
+enum Shape {
+  Circle(u32),
+  Rect { w: u8, h: u8 },
+  Empty,
+}
+
+fn main() {
+  let shape: Shape = rbmc::Arbitrary::any();
+  match shape {
+    Shape::Circle(r) => rbmc::cover(r == 7, "circle"), // satisfied
+    Shape::Rect { w, h } => rbmc::cover(w == 3 && h == 4, "rect"), // satisfied
+    Shape::Empty => rbmc::cover(true, "empty"), // satisfied
+  }
+  let p: &u8 = rbmc::nondet();
+  rbmc::cover(*p == 5, "reference"); // satisfied
+  let pair: (u8, [u8; 3]) = rbmc::Arbitrary::any();
+  rbmc::assert(pair.1[2] as u16 + pair.0 as u16 <= 510, "bounded"); // success
+  let q: Option<&i8> = rbmc::Arbitrary::any();
+  if let Some(q) = q {
+    let x = *q - 1; // fail
+  }
+}
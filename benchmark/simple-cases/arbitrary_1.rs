@@ -0,0 +1,21 @@
+// Arbitrary vectors and slices hold at most `--nondet-len` elements, 4 by
+// default.
+struct Packet<'a> {
+  header: u8,
+  payload: Vec<u8>,
+  tags: &'a [u16],
+}
+
+fn main() {
+  let packet: Packet = rbmc::nondet();
+  rbmc::assert(packet.payload.len() <= 4, "bounded payload"); // success
+  rbmc::assert(packet.tags.len() <= 4, "bounded tags"); // success
+  rbmc::cover(packet.payload.len() == 3 && packet.payload[2] == 9, "payload"); // satisfied
+  if packet.tags.len() > 1 {
+    rbmc::cover(packet.tags[1] == packet.header as u16, "tags"); // satisfied
+  }
+  let v: Vec<i8> = rbmc::Arbitrary::any();
+  if v.len() == 4 {
+    let x = v[3] - 1; // fail
+  }
+}
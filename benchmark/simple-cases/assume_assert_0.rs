@@ -0,0 +1,12 @@
+fn main() {
+  let x: u32 = rbmc::nondet();
+  rbmc::assume(x < 100);
+  let y = x + 1; // success
+  rbmc::assert(y <= 100, "y is at most 100"); // success
+  rbmc::assert(y < 100, "y is less than 100"); // fail
+  let z: u32 = rbmc::nondet();
+  if z > 5 {
+    rbmc::assume(z < 3);
+    rbmc::assert(false, "unreachable"); // success
+  }
+}
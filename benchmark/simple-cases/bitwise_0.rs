@@ -0,0 +1,16 @@
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
+
+fn main() {
+  let a = [0u8; 4];
+  let x: u32 = rbmc::nondet();
+  let h = (x ^ (x >> 16)) & 3;
+  let _ = a[h as usize]; // success
+  let y: i32 = rbmc::nondet();
+  if y < 0 {
+    let _ = a[(y % 4 + 3) as usize]; // success
+  }
+  if let std::cmp::Ordering::Less = std::intrinsics::three_way_compare(x, 5) {
+    let _ = a[x as usize]; // fail: `x` may be 4
+  }
+}
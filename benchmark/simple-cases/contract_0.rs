@@ -0,0 +1,27 @@
+// Run with `--check-contracts` to check the functions against their
+// contracts, and with `--replace-contracts` to verify `main` with the
+// contracts instead of the bodies of the functions.
+#[rbmc::requires(*p < 100)]
+#[rbmc::ensures(*p <= 100 && result == *p)]
+#[rbmc::modifies(*p)]
+fn inc(p: &mut u32) -> u32 {
+  *p += 1; // success
+  *p
+}
+
+// fail with `--check-contracts`: `result > x` does not hold for `x == 0`
+#[rbmc::requires(x < 100)]
+#[rbmc::ensures(result > x)]
+fn double(x: u32) -> u32 {
+  x * 2
+}
+
+fn main() {
+  let mut x: u32 = rbmc::nondet();
+  if x < 50 {
+    let y = inc(&mut x);
+    let _ = 100 - y; // success
+  }
+  let z: u32 = rbmc::nondet();
+  let _ = double(z); // fail with `--replace-contracts`: the precondition
+}
@@ -0,0 +1,12 @@
+// Covers are reported as satisfied or unsatisfiable, and do not change the
+// verdict. Run with `--assertion-reach-checks` to report the dead assertion.
+fn main() {
+  let x: u32 = rbmc::nondet();
+  rbmc::assume(x < 100);
+  rbmc::cover(x == 42, "x can be 42"); // satisfied
+  rbmc::cover(x == 100, "x can be 100"); // unsatisfiable
+  rbmc::assert(x < 100, "x is less than 100"); // success
+  if x > 200 {
+    rbmc::assert(false, "dead assertion"); // unreachable
+  }
+}
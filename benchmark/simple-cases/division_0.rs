@@ -0,0 +1,12 @@
+fn main() {
+  let x: i32 = rbmc::nondet();
+  let y: i32 = rbmc::nondet();
+  if y > 0 {
+    let z = x / y; // success
+    let _ = z;
+  }
+  if y != 0 {
+    let z = x / y; // fail: `i32::MIN / -1` overflows
+    let _ = z;
+  }
+}
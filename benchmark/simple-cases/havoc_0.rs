@@ -0,0 +1,12 @@
+extern "C" {
+  fn fill(p: *mut i32) -> i32;
+}
+
+fn main() {
+  let mut x: i32 = 1;
+  let r = unsafe { fill(&mut x as *mut i32) };
+  if r == 0 {
+    let z = 100 / x; // fail with `--havoc`: x is havocked
+    let _ = z;
+  }
+}
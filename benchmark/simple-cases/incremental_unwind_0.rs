@@ -0,0 +1,12 @@
+// Run with `--incremental-unwind`: the bounds are increased until the
+// division by zero is found.
+fn main() {
+  let n: u32 = rbmc::nondet();
+  let mut i = 0;
+  let mut s = 0u32;
+  while i < n {
+    s = s + 2;
+    i += 1;
+  }
+  let _ = 100 / (6 - s); // fail
+}
@@ -0,0 +1,8 @@
+fn main() {
+  let a = [1u8; 2];
+  let x: i8 = rbmc::nondet();
+  if x < 0 {
+    // `x as u8` is at least 128 in bitvector encoding
+    let _ = a[(x as u8 / 128) as usize]; // fail with `--int-encoding int`
+  }
+}
@@ -0,0 +1,11 @@
+// Run with `--k-induction`: the loop is unbounded, but the property is
+// 1-inductive.
+fn main() {
+  let mut x: u32 = 0;
+  loop {
+    let b: bool = rbmc::nondet();
+    if b { break; }
+    if x == 10 { x = 0; } else { x += 1; }
+    let _ = 100 / (11 - x); // success
+  }
+}
@@ -0,0 +1,12 @@
+// The loop is checked once with its invariant instead of being unwound.
+fn main() {
+  let n: u32 = rbmc::nondet();
+  let mut i: u32 = 0;
+  loop {
+    rbmc::loop_invariant(i <= n);
+    if i >= n { break; }
+    i += 1;
+  }
+  let _ = 100 / (n - i + 1); // success
+  let _ = 100 / (n - i - 1); // fail
+}
@@ -0,0 +1,12 @@
+fn main() {
+  let i: usize = rbmc::nondet_range(2, 10);
+  rbmc::assert(i >= 2 && i < 10, "in range"); // success
+  let a: [u8; 4] = rbmc::nondet_array();
+  let s: &[u8] = rbmc::nondet_slice(3);
+  rbmc::assert(s.len() <= 3, "bounded length"); // success
+  rbmc::cover(s.len() == 3 && s[2] == 7, "full slice"); // satisfied
+  if s.len() > 0 {
+    let x = s[0] as u16 + a[1] as u16; // success
+  }
+  let y = s[2]; // fail
+}
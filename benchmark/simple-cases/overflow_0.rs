@@ -0,0 +1,10 @@
+fn main() {
+  let x: i32 = rbmc::nondet();
+  if x < 100 {
+    let y = x + 1; // success
+    let _ = y;
+  }
+  let z: i8 = rbmc::nondet();
+  let w = -z; // fail: `-i8::MIN` overflows
+  let _ = w;
+}
@@ -0,0 +1,24 @@
+// Wrapping and checked arithmetic never overflow. Only the plain operators in
+// the local crate are checked.
+fn fnv(bytes: [u8; 2]) -> u32 {
+  let mut hash: u32 = 0x811c9dc5;
+  hash ^= bytes[0] as u32;
+  hash = hash.wrapping_mul(0x01000193); // success
+  hash ^= bytes[1] as u32;
+  hash.wrapping_mul(0x01000193) // success
+}
+
+fn main() {
+  let x: u8 = rbmc::nondet();
+  let _ = x.wrapping_add(1); // success
+  match x.checked_add(10) {
+    Some(z) => rbmc::assert(z >= 10, "checked"), // success
+    None => rbmc::assert(x > 245, "checked overflow"), // success
+  }
+  let (w, overflow) = x.overflowing_sub(1); // success
+  rbmc::assert(overflow == (x == 0), "overflowing"); // success
+  let _ = w;
+  let bytes: [u8; 2] = rbmc::nondet();
+  let _ = fnv(bytes);
+  let _ = x + 1; // fail
+}
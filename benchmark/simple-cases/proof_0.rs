@@ -0,0 +1,19 @@
+fn half(x: u8) -> u8 {
+  x / 2
+}
+
+#[rbmc::proof]
+fn check_half() {
+  let x: u8 = rbmc::nondet();
+  let y = half(x) + 100; // success
+  let _ = y;
+}
+
+#[rbmc::proof]
+fn check_double() {
+  let x: u8 = rbmc::nondet();
+  let y = x * 2; // fail
+  let _ = y;
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+fn sum(n: u32) -> u32 {
+  if n == 0 { 0 } else { 1 + sum(n - 1) }
+}
+
+fn main() {
+  let n: u32 = rbmc::nondet();
+  // fail with `--recursion-bound 3 --unwinding-assertions`
+  // success with `--recursion-bound 6 --unwinding-assertions`
+  if n <= 5 {
+    let s = sum(n);
+    let _ = 10 / (11 - s); // success
+  }
+}
@@ -0,0 +1,15 @@
+fn main() {
+  let lit = "héllo";
+  rbmc::assert(lit.len() == 6, "bytes"); // success
+  let mut s = String::with_capacity(2);
+  s.push_str("ab");
+  s.push_str(lit);
+  rbmc::assert(s.len() == 8 && s.capacity() == 8, "grow"); // success
+  rbmc::assert(s == "abhéllo" && s != "abhello", "eq"); // success
+  rbmc::assert(&s[3..5] == "é" && s.as_bytes()[3] == 0xc3, "utf-8"); // success
+  let i: usize = rbmc::nondet_range(0, 4);
+  let t = &lit[..i]; // fail: i == 2
+  rbmc::assert(t.len() == i, "prefix"); // success
+  let u = String::from(lit);
+  rbmc::assert(u == "hello", "eq"); // fail
+}
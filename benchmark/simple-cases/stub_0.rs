@@ -0,0 +1,26 @@
+// The loop in `checksum` is not unwound, because calls to `checksum` go to
+// its stub. Run with `--stub checksum=small` to replace it by `small`.
+fn checksum(n: u32) -> u32 {
+  let mut s: u32 = 0;
+  let mut i = 0;
+  while i < n {
+    s = s.wrapping_mul(31).wrapping_add(i);
+    i += 1;
+  }
+  s
+}
+
+#[rbmc::stub(checksum)]
+fn any_checksum(_n: u32) -> u32 {
+  rbmc::nondet()
+}
+
+fn small(_n: u32) -> u32 {
+  7
+}
+
+fn main() {
+  let n: u32 = rbmc::nondet();
+  let s = checksum(n);
+  let _ = 100 / (s + 1); // fail, success with `--stub checksum=small`
+}
@@ -0,0 +1,6 @@
+fn main() {
+  let x: u32 = rbmc::nondet();
+  let y = x / 2; // success
+  let f = y as f64; // unknown: floats are not supported
+  let _ = f;
+}
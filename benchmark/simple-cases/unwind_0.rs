@@ -0,0 +1,24 @@
+// Run with `--unwind 2 --unwinding-assertions`: the loop in `sum` is bounded
+// by its attribute, and the loop in `main` needs `--unwindset main=21`.
+#[rbmc::unwind(4)]
+fn sum(n: u32) -> u32 {
+  let mut s = 0;
+  let mut i = 0;
+  while i < n { // success
+    s += 1;
+    i += 1;
+  }
+  s
+}
+
+fn main() {
+  let n: u32 = rbmc::nondet();
+  if n <= 3 {
+    let s = sum(n);
+    let mut j = 0;
+    while j < 20 { // fail
+      j += 1;
+    }
+    let _ = 10 / (s + j); // success
+  }
+}
@@ -0,0 +1,13 @@
+fn main() {
+  let n: u32 = rbmc::nondet();
+  let mut i = 0;
+  let mut s = 0u32;
+  // fail with `--unwind 3 --unwinding-assertions`
+  // success with `--unwind 6 --unwinding-assertions`
+  while i < n && i < 5 {
+    s = s + 2;
+    i += 1;
+  }
+  let z = 100 / (11 - s); // success
+  let _ = z;
+}
@@ -0,0 +1,22 @@
+fn main() {
+  let mut v: Vec<u8> = Vec::new();
+  v.push(1);
+  v.insert(0, 3);
+  rbmc::assert(v.len() == 2 && v.capacity() == 8, "amortized growth"); // success
+  v[1] = 5;
+  let r = v.remove(0);
+  rbmc::assert(r == 3 && v[0] == 5, "remove"); // success
+  if let Some(x) = v.pop() {
+    rbmc::assert(x == 5 && v.len() == 0, "pop"); // success
+  }
+  let y = v[0]; // fail
+  v.insert(1, 0); // fail
+  let w: Vec<i32> = rbmc::nondet_vec(3);
+  rbmc::assert(w.len() <= 3, "bounded length"); // success
+  if w.len() == 2 {
+    rbmc::assert(w[1] != 5, "element"); // fail
+  }
+  let mut u: Vec<u8> = Vec::new();
+  u.push(1);
+  while let Some(_) = u.pop() {} // success
+}
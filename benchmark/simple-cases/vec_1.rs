@@ -0,0 +1,25 @@
+// Elements of a vector with a symbolic length are moved up to the bound of
+// its length, and shared borrows at symbolic indices point to copies.
+fn main() {
+  let mut v: Vec<u8> = rbmc::nondet_vec(4);
+  let n = v.len();
+  let x: u8 = rbmc::nondet();
+  v.insert(0, x);
+  rbmc::assert(v.len() == n + 1 && v[0] == x, "insert"); // success
+  let i: usize = rbmc::nondet();
+  rbmc::assume(i < n);
+  let old = v[i + 1];
+  let y = v.remove(0);
+  rbmc::assert(y == x && v[i] == old, "remove"); // success
+  match v.get(i) {
+    Some(e) => rbmc::assert(*e == old, "get"), // success
+    None => rbmc::assert(false, "get in bounds"), // success
+  }
+  let s: &[u8] = rbmc::nondet_slice(3);
+  v.extend_from_slice(s);
+  rbmc::assert(v.len() == n + s.len(), "extend"); // success
+  if s.len() > 1 {
+    rbmc::assert(v[n + 1] == s[1], "extended"); // success
+  }
+  rbmc::assert(v[i] != 7, "element"); // fail
+}
@@ -99,6 +99,7 @@ fn pop() {
 
 extern crate rbmc;
 
+#[rbmc::unwind(12)]
 fn main() {
     while unsafe { 
         !S.is_null() || pc1 != 1 || pc4 != 1
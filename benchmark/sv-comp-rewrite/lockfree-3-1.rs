@@ -104,6 +104,7 @@ fn pop() {
 
 extern crate rbmc;
 
+#[rbmc::unwind(7)]
 fn main() {
     while unsafe { 
         pc1 != 1 || pc4 != 1
@@ -104,6 +104,7 @@ fn pop() {
 
 extern crate rbmc;
 
+#[rbmc::unwind(11)]
 fn main() {
     while unsafe { 
         !S.is_null() || pc1 != 1
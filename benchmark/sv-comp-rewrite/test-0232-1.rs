@@ -26,6 +26,7 @@ fn append(plist : *mut *mut item) {
 
 extern crate rbmc;
 
+#[rbmc::unwind(1)]
 fn main() {
     let mut list : *mut item = ptr::null_mut();
 
@@ -7,20 +7,24 @@ RBMC_OUTPUT = OUT_DIR + "rbmc/"
 KANI_OUTPUT = OUT_DIR + "kani/"
 ESBMC_OUTPUT = OUT_DIR + "esbmc/"
 
+# Loop bounds of C files for ESBMC. Rust files set them by `#[rbmc::unwind]`.
 loop_bound = {
-  "lockfree-3-0": 12,
-  "lockfree-3-1": 7,
-  "lockfree-3-2": 7,
-  "lockfree-3-3": 11,
-  "test-0232-1": 1,
-  "test-0232-2": 10,
-  "test-0232-3": 3,
-
-  # C file
   "lockfree-3.0": 12,
   "lockfree-3.1": 7,
   "lockfree-3.2": 7,
   "lockfree-3.3": 11,
+  "test-0232-1": 1,
+  "test-0232-2": 10,
+  "test-0232-3": 3,
+}
+
+# Exit codes of rbmc
+rbmc_verdict = {
+  0: "success",
+  10: "fail",
+  11: "unknown",
+  12: "unsupported",
+  13: "error",
 }
 
 def run_on_single_file(cmd, smt_strategy):
@@ -31,7 +35,7 @@ def run_on_single_file(cmd, smt_strategy):
   extra_args = []
   
   # set bound
-  if crate in loop_bound:
+  if cmd[1].endswith(".c") and crate in loop_bound:
     extra_args.append("--unwind")
     extra_args.append(str(loop_bound[crate]))
 
@@ -44,7 +48,10 @@ def run_on_single_file(cmd, smt_strategy):
   extra_args += [">", log_file, "2>&1"]
   final_cmd = cmd + extra_args
   print("Go " + " ".join(final_cmd))
-  os.system(" ".join(final_cmd))
+  status = os.system(" ".join(final_cmd))
+  if cmd[0] == "rbmc":
+    code = os.waitstatus_to_exitcode(status)
+    print("Verdict: " + rbmc_verdict.get(code, "error"))
 
 def rbmc(file):
   assert(os.path.exists(file))
@@ -62,7 +69,7 @@ def kani(file):
       if line.startswith("fn main() {"):
         code.append("#[kani::proof]\n")
       if "extern crate rbmc;" in line: continue
-      code.append(line.replace("rbmc::nondet", "kani::any"))
+      code.append(line.replace("rbmc::nondet", "kani::any").replace("rbmc::unwind", "kani::unwind"))
   # crate a tmp file
   tmp_file = os.path.join(KANI_OUTPUT, os.path.basename(file))
   with open(tmp_file, "w") as crate: crate.write("".join(code))
@@ -1,9 +1,13 @@
 use std::cell::RefCell;
 
+use super::report::*;
+use super::verdict::*;
 use crate::config::cli::SmtStrategy;
 use crate::config::config::Config;
-use crate::expr::expr::ExprBuilder;
+use crate::config::unwind::Unwind;
+use crate::expr::expr::*;
 use crate::solvers::solver::*;
+use crate::symbol::nstring::NString;
 use crate::symex::symex::*;
 use crate::vc::slicer::Slicer;
 use crate::vc::vc::*;
@@ -13,56 +17,238 @@ pub struct Bmc<'cfg> {
     symex: Symex<'cfg>,
     vc_system: VCSysPtr,
     runtime_solver: Solver<'cfg>,
+    report: HarnessReport,
 }
 
 impl<'cfg> Bmc<'cfg> {
-    pub fn new(config: &'cfg Config) -> Self {
+    pub fn new(config: &'cfg Config, entry: NString) -> Self {
+        Bmc::with_unwind(config, entry, config.unwind())
+    }
+
+    pub fn with_unwind(config: &'cfg Config, entry: NString, unwind: Unwind) -> Self {
         let vc_system = VCSysPtr::new(RefCell::new(VCSystem::new(config.expr_ctx.clone())));
-        let symex = Symex::new(config, vc_system.clone());
-        let runtime_solver = Solver::new(&config.solver_config);
-        Bmc { config, symex, vc_system, runtime_solver }
+        let symex = Symex::new(config, vc_system.clone(), entry, unwind);
+        let runtime_solver = Solver::new(&config.solver_config, config.cli.int_encoding);
+        let report = HarnessReport { harness: entry.to_string(), ..Default::default() };
+        Bmc { config, symex, vc_system, runtime_solver, report }
     }
 
-    pub fn do_bmc(&mut self) {
-        if self.config.cli.show_program || self.config.cli.program_only {
-            self.config.program.show(!self.config.cli.show_std_function);
-            if self.config.cli.program_only {
-                return;
+    /// Verify the entry functions in turn. A verdict table is shown if there
+    /// are more than one entry functions.
+    pub fn run(config: &'cfg Config, entries: Vec<NString>) -> Verdict {
+        if config.cli.show_program || config.cli.program_only {
+            config.program.show(!config.cli.show_std_function);
+            if config.cli.program_only {
+                return Verdict::Skipped;
+            }
+        }
+
+        let is_text = config.is_text_output();
+        let crate_name = stable_mir::local_crate().name;
+        let mut results = Vec::new();
+        let mut reports = Vec::new();
+        for &entry in entries.iter() {
+            if is_text && entries.len() > 1 {
+                println!("\nHarness: {entry:?}");
+            }
+            let (verdict, report) = if config.cli.k_induction {
+                Bmc::k_induction(config, entry)
+            } else if config.cli.incremental_unwind {
+                Bmc::incremental(config, entry)
+            } else {
+                let mut bmc = Bmc::new(config, entry);
+                (bmc.do_bmc(), bmc.report)
+            };
+            results.push((entry, verdict));
+            reports.push(report);
+        }
+
+        // Library crates may contain neither harnesses nor `main`
+        let verdict = results.iter().map(|(_, verdict)| *verdict).max().unwrap_or(Verdict::Skipped);
+
+        if !is_text {
+            let report = Report { crate_name, result: verdict, harnesses: reports };
+            println!("{}", serde_json::to_string(&report).unwrap());
+        } else if entries.is_empty() {
+            println!("No entry function in crate {crate_name}");
+            println!("\nVerification result: {verdict:?}.");
+        } else if entries.len() > 1 {
+            let width = results.iter().map(|(entry, _)| entry.len()).max().unwrap().max(7);
+            println!("\n{:<width$}  Result", "Harness");
+            for (entry, verdict) in results.iter() {
+                println!("{:<width$}  {verdict:?}", entry.to_string());
             }
+            println!("\nVerification result: {verdict:?}.");
         }
+        verdict
+    }
+
+    /// Verify `entry` with the loop bounds 1, 2, ... Each round checks the
+    /// properties together with unwinding assertions. If some of them fail,
+    /// the round is repeated without unwinding assertions to tell whether a
+    /// property fails within the bound. Recursions are bounded in the same
+    /// way unless `--recursion-bound` is set.
+    fn incremental(config: &'cfg Config, entry: NString) -> (Verdict, HarnessReport) {
+        let default = config.unwind();
+        let mut rounds = Rounds::new(config, entry, "Incremental unwinding");
+        let mut bound = 1;
+        let (verdict, report) = loop {
+            let recursion_bound =
+                if default.recursion_bound == 0 { bound } else { default.recursion_bound };
+            let unwind = Unwind { bound, recursion_bound, assertions: true };
+            let checked = rounds.run(unwind, None);
+            if checked.0 != Verdict::Fail {
+                // The bound is enough, or nothing is known
+                break checked;
+            }
+            let assumed = rounds.run(Unwind { assertions: false, ..unwind }, None);
+            if assumed.0 != Verdict::Success {
+                break assumed;
+            }
+            if rounds.is_exhausted(bound) {
+                // The same verdict as verifying with the last bound
+                break if default.assertions { checked } else { assumed };
+            }
+            bound += 1;
+        };
+        rounds.finish(verdict, report)
+    }
+
+    /// Prove `entry` by k-induction with k = 1, 2, ... The base case is
+    /// checked as `--incremental-unwind` does. The inductive step havocs the
+    /// places written in a loop when the loop is entered, assumes that the
+    /// properties hold in `k` iterations and checks them in the next one.
+    fn k_induction(config: &'cfg Config, entry: NString) -> (Verdict, HarnessReport) {
+        let default = config.unwind();
+        let mut rounds = Rounds::new(config, entry, "k-induction");
+        let mut k = 1;
+        let (verdict, mut report) = loop {
+            let recursion_bound =
+                if default.recursion_bound == 0 { k } else { default.recursion_bound };
+            // The properties checked in the last iteration are assumed away
+            // with the paths exceeding the bound, so the base case runs one
+            // more iteration than the inductive step assumes.
+            let unwind = Unwind { bound: k + 1, recursion_bound, assertions: true };
+            let checked = rounds.run(unwind, None);
+            if checked.0 != Verdict::Fail {
+                break checked;
+            }
+            let base = rounds.run(Unwind { assertions: false, ..unwind }, None);
+            if base.0 != Verdict::Success {
+                break base;
+            }
+            let step = rounds.run(unwind, Some(k));
+            if matches!(step.0, Verdict::Success | Verdict::Unsupported) {
+                break step;
+            }
+            if rounds.is_exhausted(k) {
+                // The properties hold within the bound, but are not proved
+                break (Verdict::Unknown, step.1);
+            }
+            k += 1;
+        };
+        report.result = verdict;
+        rounds.finish(verdict, report)
+    }
+
+    fn is_text(&self) -> bool {
+        self.config.is_text_output()
+    }
 
+    pub fn do_bmc(&mut self) -> Verdict {
         let verify_time = std::time::Instant::now();
-        println!("Start Symex ...");
+        if self.is_text() {
+            println!("Start Symex ...");
+        }
 
         self.symex.run();
-        println!("Runtime Symex: {}s", verify_time.elapsed().as_secs_f32());
-
-        self.vc_system.borrow().show_info();
+        self.report.statistics.symex_time = verify_time.elapsed().as_secs_f32();
+        if self.is_text() {
+            println!("Runtime Symex: {}s", verify_time.elapsed().as_secs_f32());
+            self.vc_system.borrow().show_info();
+        }
+        self.report.statistics.symex_steps = self.vc_system.borrow().num_step();
+        self.report.statistics.vccs = self.vc_system.borrow().num_asserts();
+        self.init_properties();
+        self.havoc_warning();
 
-        let res = if self.vc_system.borrow().num_asserts() == 0 {
-            println!("No assertion should be checked");
-            PResult::PUnsat
+        let verdict = if !self.symex.unsupported().is_empty() {
+            // The properties are not checked since the program is not
+            // completely encoded.
+            self.unsupported_report();
+            Verdict::Unsupported
+        } else if self.vc_system.borrow().num_asserts() == 0 {
+            if self.is_text() {
+                println!("No assertion should be checked");
+            }
+            Verdict::Success
         } else {
-            self.check_properties()
+            Verdict::from(self.check_properties())
         };
+        if self.symex.unsupported().is_empty() {
+            self.check_covers();
+        }
 
-        println!("\nVerification time: {}s", verify_time.elapsed().as_secs_f32());
-        println!(
-            "Verification result: {}.",
-            match res {
-                PResult::PSat => "fail",
-                PResult::PUnknow => "unknown",
-                PResult::PUnsat => "success",
+        self.report.result = verdict;
+        self.report.statistics.verification_time = verify_time.elapsed().as_secs_f32();
+        if self.is_text() {
+            println!("\nVerification time: {}s", verify_time.elapsed().as_secs_f32());
+            println!("Verification result: {verdict:?}.");
+        }
+        verdict
+    }
+
+    fn havoc_warning(&mut self) {
+        self.report.havocked = self.symex.havocked().iter().map(|f| f.to_string()).collect();
+        if self.is_text() && !self.report.havocked.is_empty() {
+            println!("\nWarning: calls to the following functions are havocked:");
+            for function in self.report.havocked.iter() {
+                println!("  {function}");
             }
-        );
+        }
+    }
+
+    fn unsupported_report(&mut self) {
+        let unsupported = self.symex.unsupported();
+        self.report.unsupported = unsupported.iter().map(UnsupportedReport::from).collect();
+        if self.is_text() {
+            println!("\nUnsupported features:");
+            for report in self.report.unsupported.iter() {
+                let function = report.function.as_deref().unwrap_or("?");
+                let location = report
+                    .span
+                    .as_ref()
+                    .map_or("?".to_string(), |s| format!("{}:{}:{}", s.file, s.line, s.column));
+                println!("  {} in {function} at {location}", report.construct);
+            }
+        }
+    }
+
+    /// All properties are `unknown` before checking
+    fn init_properties(&mut self) {
+        let vc_system = self.vc_system.borrow();
+        self.report.properties = (0..vc_system.num_asserts())
+            .map(|n| {
+                let assertion = vc_system.nth_assertion(n);
+                PropertyReport {
+                    message: format!("{:?}", assertion.msg()),
+                    status: Verdict::Unknown,
+                    span: assertion.span.map(SpanReport::from),
+                }
+            })
+            .collect();
     }
 
     fn check_properties(&mut self) -> PResult {
-        println!("Verifying with SMT strategy: {:?}", self.config.cli.smt_strategy);
+        if self.is_text() {
+            println!("Verifying with SMT strategy: {:?}", self.config.cli.smt_strategy);
+        }
         let (res, bug) = match self.config.cli.smt_strategy {
             SmtStrategy::Forward => self.check_forward(),
             SmtStrategy::Once => (self.check_once(), None),
         };
+        self.report.statistics.sliced_steps =
+            self.vc_system.borrow().iter().filter(|step| step.is_sliced).count();
         if res == PResult::PSat {
             self.bug_report(bug);
         }
@@ -73,7 +259,9 @@ impl<'cfg> Bmc<'cfg> {
         let mut slicer = Slicer::default();
         let size = self.vc_system.borrow().num_asserts();
         for i in 0..size {
-            println!("Begin checking assertion {i}");
+            if self.is_text() {
+                println!("Begin checking assertion {i}");
+            }
             if self.config.cli.show_vcc {
                 print!("Verifying condition {i} ");
             }
@@ -83,25 +271,24 @@ impl<'cfg> Bmc<'cfg> {
             if !self.config.cli.no_slice {
                 let slice_time = std::time::Instant::now();
                 slicer.slice_nth(self.vc_system.clone(), i);
-                println!("Runtime slicing asssertion {i}: {}s", slice_time.elapsed().as_secs_f32());
-                println!("After slicing: {} steps", self.vc_system.borrow().num_step());
+                if self.is_text() {
+                    println!(
+                        "Runtime slicing asssertion {i}: {}s",
+                        slice_time.elapsed().as_secs_f32()
+                    );
+                    println!("After slicing: {} steps", self.vc_system.borrow().num_step());
+                }
             }
 
             if self.config.cli.show_vcc {
                 self.vc_system.borrow().show_vcc();
             }
 
-            self.runtime_solver.reset();
-            let convert_time = std::time::Instant::now();
-            self.generate_smt_formula();
-            println!("Runtime Convert SSA: {}s", convert_time.elapsed().as_secs_f32());
-
-            let solver_time = std::time::Instant::now();
-            let res = self.smt_result();
-            println!("Runtime SMT check: {}s", solver_time.elapsed().as_secs_f32());
+            let res = self.check_formula();
             if self.config.cli.show_vcc {
                 println!("Result: {res:?} ");
             }
+            self.report.properties[i].status = Verdict::from(res);
             match res {
                 PResult::PSat => return (res, Some(i)),
                 PResult::PUnknow => return (res, None),
@@ -112,7 +299,9 @@ impl<'cfg> Bmc<'cfg> {
     }
 
     fn check_once(&mut self) -> PResult {
-        println!("Begin checking all assertions at once");
+        if self.is_text() {
+            println!("Begin checking all assertions at once");
+        }
         if self.config.cli.show_vcc {
             println!("Verifying condition:");
         }
@@ -120,24 +309,112 @@ impl<'cfg> Bmc<'cfg> {
             let mut slicer = Slicer::default();
             let slice_time = std::time::Instant::now();
             slicer.slice_whole(self.vc_system.clone());
-            println!("Runtime slicing asssertion: {}s", slice_time.elapsed().as_secs_f32());
-            println!("After slicing: {} steps", self.vc_system.borrow().num_step());
+            if self.is_text() {
+                println!("Runtime slicing asssertion: {}s", slice_time.elapsed().as_secs_f32());
+                println!("After slicing: {} steps", self.vc_system.borrow().num_step());
+            }
         }
 
         if self.config.cli.show_vcc {
             self.vc_system.borrow().show_vcc();
         }
 
+        let res = self.check_formula();
+        if self.config.cli.show_vcc {
+            print!("Result: {res:?} ");
+        }
+        // The model only tells which properties fail
+        for n in 0..self.report.properties.len() {
+            let status = match res {
+                PResult::PSat => {
+                    let assertion = self.vc_system.borrow().nth_assertion(n);
+                    if self.runtime_solver.eval_bool(assertion.cond()) {
+                        PResult::PSat
+                    } else {
+                        PResult::PUnknow
+                    }
+                }
+                _ => res,
+            };
+            self.report.properties[n].status = Verdict::from(status);
+        }
+        res
+    }
+
+    /// Check whether each cover is satisfiable with the steps before it.
+    /// Covers do not change the verdict.
+    fn check_covers(&mut self) {
+        let num_covers = self.vc_system.borrow().num_covers();
+        if num_covers == 0 {
+            return;
+        }
+        for n in 0..num_covers {
+            // A cover only depends on the steps before it. Assertions are
+            // not assumed, and slicing is ignored.
+            self.runtime_solver.reset();
+            let vc_system = self.vc_system.borrow();
+            let position = vc_system.nth_cover_position(n);
+            for step in vc_system.iter().take(position) {
+                match &step.kind {
+                    StepKind::Assign(lhs, rhs) => {
+                        self.runtime_solver.assert_assign(lhs.clone(), rhs.clone())
+                    }
+                    StepKind::Assume(c) => self.runtime_solver.assert_expr(c.clone()),
+                    _ => {}
+                }
+            }
+            let cover = vc_system.nth_cover(n);
+            drop(vc_system);
+            let StepKind::Cover(kind, msg, cond) = &cover.kind else { unreachable!() };
+            self.runtime_solver.assert_expr(cond.clone());
+            let solver_time = std::time::Instant::now();
+            let res = self.runtime_solver.check();
+            self.report.statistics.solver_time += solver_time.elapsed().as_secs_f32();
+            let status = match kind {
+                CoverKind::Cover => format!("{:?}", CoverResult::from(res)),
+                CoverKind::Reach => match res {
+                    PResult::PSat => "reachable".to_string(),
+                    PResult::PUnsat => "unreachable".to_string(),
+                    PResult::PUnknow => format!("{:?}", CoverResult::Unknown),
+                },
+            };
+            self.report.covers.push(CoverReport {
+                kind: match kind {
+                    CoverKind::Cover => "cover".to_string(),
+                    CoverKind::Reach => "reach".to_string(),
+                },
+                message: format!("{msg:?}"),
+                status,
+                span: cover.span.map(SpanReport::from),
+            });
+        }
+        if self.is_text() {
+            println!("\nCovers:");
+            for report in self.report.covers.iter() {
+                let location = report
+                    .span
+                    .as_ref()
+                    .map_or("?".to_string(), |s| format!("{}:{}:{}", s.file, s.line, s.column));
+                println!("  -> {location}: {}: {}", report.message, report.status);
+            }
+        }
+    }
+
+    /// Encode the unsliced steps and invoke the solver
+    fn check_formula(&mut self) -> PResult {
         self.runtime_solver.reset();
         let convert_time = std::time::Instant::now();
         self.generate_smt_formula();
-        println!("Runtime Convert SSA: {}s", convert_time.elapsed().as_secs_f32());
+        self.report.statistics.convert_time += convert_time.elapsed().as_secs_f32();
+        if self.is_text() {
+            println!("Runtime Convert SSA: {}s", convert_time.elapsed().as_secs_f32());
+        }
 
         let solver_time = std::time::Instant::now();
         let res = self.smt_result();
-        println!("Runtime SMT check: {}s", solver_time.elapsed().as_secs_f32());
-        if self.config.cli.show_vcc {
-            print!("Result: {res:?} ");
+        self.report.statistics.solver_time += solver_time.elapsed().as_secs_f32();
+        if self.is_text() {
+            println!("Runtime SMT check: {}s", solver_time.elapsed().as_secs_f32());
         }
         res
     }
@@ -151,7 +428,9 @@ impl<'cfg> Bmc<'cfg> {
     }
 
     fn generate_smt_formula(&mut self) {
-        println!("Converting SSA");
+        if self.is_text() {
+            println!("Converting SSA");
+        }
         let ctx = self.config.expr_ctx.clone();
 
         // let mut assume = ctx._true();
@@ -173,6 +452,7 @@ impl<'cfg> Bmc<'cfg> {
                     self.runtime_solver.assert_expr(c.clone());
                     // assume = ctx.and(assume, c.clone());
                 }
+                StepKind::Cover(..) => {}
             }
         }
 
@@ -181,22 +461,70 @@ impl<'cfg> Bmc<'cfg> {
         self.runtime_solver.assert_expr(assert);
     }
 
-    fn bug_report(&self, bug: Option<usize>) {
-        println!("\nBug Report:");
-        if self.config.cli.smt_strategy == SmtStrategy::Forward {
-            let assertion = self.vc_system.borrow().nth_assertion(bug.unwrap());
-            Bmc::bug_info(&assertion);
+    fn bug_report(&mut self, bug: Option<usize>) {
+        let bug = if self.config.cli.smt_strategy == SmtStrategy::Forward {
+            bug
         } else {
-            for n in 0..self.vc_system.borrow().num_asserts() {
-                let assertion = self.vc_system.borrow().nth_assertion(n);
-                if self.runtime_solver.eval_bool(assertion.cond()) {
-                    Bmc::bug_info(&assertion);
-                    // Only show the first violated property
-                    break;
-                }
+            // Only show the first violated property
+            self.report.properties.iter().position(|property| property.status == Verdict::Fail)
+        };
+        let Some(n) = bug else { return };
+        let trace = self.counterexample(n);
+        if self.is_text() {
+            println!("\nBug Report:");
+            Bmc::bug_info(&self.vc_system.borrow().nth_assertion(n));
+            println!("\nCounterexample:");
+            for step in trace.iter() {
+                let line = step.line.map_or("?".to_string(), |line| line.to_string());
+                let nondet = if step.nondet { "  <- nondet" } else { "" };
+                println!(
+                    "  {} line {line}: {} = {}{nondet}",
+                    step.function, step.variable, step.value
+                );
             }
+            println!("");
         }
-        println!("");
+        self.report.counterexample = Some(trace);
+    }
+
+    /// The values of the variables in the source code, which lead to the
+    /// failure of the `n`-th assertion.
+    fn counterexample(&self, n: usize) -> Vec<TraceStep> {
+        let program = &self.config.program;
+        let vc_system = self.vc_system.borrow();
+        let m = vc_system.nth_assertion_position(n);
+        let mut trace = Vec::new();
+        for step in vc_system.iter().take(m) {
+            let StepKind::Assign(lhs, rhs) = &step.kind else { continue };
+            if step.is_sliced || !lhs.is_symbol() {
+                continue;
+            }
+            let symbol = lhs.extract_symbol();
+            if !symbol.is_stack_symbol() || !program.is_local_function(symbol.function()) {
+                continue;
+            }
+            let function = program.function(program.function_id(symbol.function()));
+            let Some(name) = function.local_name(symbol.local()) else { continue };
+            let Some(value) = self.runtime_solver.eval_value(lhs.clone()) else { continue };
+            trace.push(TraceStep {
+                function: symbol.function().to_string(),
+                line: step.span.map(|span| span.get_lines().start_line),
+                variable: name.to_string(),
+                value: value.to_string(),
+                nondet: Bmc::is_nondet(rhs),
+            });
+        }
+        trace
+    }
+
+    /// Whether the value comes from `rbmc::nondet`
+    fn is_nondet(expr: &Expr) -> bool {
+        if expr.is_symbol() {
+            let symbol = expr.extract_symbol();
+            return symbol.is_global_symbol()
+                && symbol.ident().to_nstring().starts_with("nondet_".into());
+        }
+        expr.sub_exprs().iter().any(Bmc::is_nondet)
     }
 
     #[inline]
@@ -211,3 +539,58 @@ impl<'cfg> Bmc<'cfg> {
         );
     }
 }
+
+/// Rounds of `--incremental-unwind` and `--k-induction`
+struct Rounds<'cfg> {
+    config: &'cfg Config,
+    entry: NString,
+    name: &'static str,
+    start: std::time::Instant,
+    reports: Vec<RoundReport>,
+}
+
+impl<'cfg> Rounds<'cfg> {
+    fn new(config: &'cfg Config, entry: NString, name: &'static str) -> Self {
+        Rounds { config, entry, name, start: std::time::Instant::now(), reports: Vec::new() }
+    }
+
+    /// Verify with the default bounds `unwind`, or the inductive step of `k`
+    fn run(&mut self, unwind: Unwind, induction: Option<usize>) -> (Verdict, HarnessReport) {
+        let mut round = RoundReport {
+            unwind: induction.unwrap_or(unwind.bound),
+            unwinding_assertions: unwind.assertions && induction.is_none(),
+            inductive_step: induction.is_some(),
+            result: Verdict::Unknown,
+        };
+        if self.config.is_text_output() {
+            println!("\n{}: {}", self.name, round.label());
+        }
+        let mut bmc = Bmc::with_unwind(self.config, self.entry, unwind);
+        if let Some(k) = induction {
+            bmc.symex.set_induction(k);
+        }
+        let verdict = bmc.do_bmc();
+        round.result = verdict;
+        self.reports.push(round);
+        (verdict, bmc.report)
+    }
+
+    /// Whether the bound reaches `--max-unwind` or the time is over
+    fn is_exhausted(&self, bound: usize) -> bool {
+        let cli = &self.config.cli;
+        bound >= cli.max_unwind
+            || cli.unwind_time_limit.is_some_and(|limit| self.start.elapsed().as_secs() >= limit)
+    }
+
+    fn finish(self, verdict: Verdict, mut report: HarnessReport) -> (Verdict, HarnessReport) {
+        if self.config.is_text_output() {
+            println!("\n{}:", self.name);
+            for round in self.reports.iter() {
+                println!("  {}: {:?}", round.label(), round.result);
+            }
+            println!("Verification result: {verdict:?}.");
+        }
+        report.rounds = self.reports;
+        (verdict, report)
+    }
+}
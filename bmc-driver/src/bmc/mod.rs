@@ -1 +1,3 @@
 pub mod bmc;
+pub mod report;
+pub mod verdict;
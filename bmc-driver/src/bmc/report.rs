@@ -0,0 +1,131 @@
+use serde::Serialize;
+use stable_mir::ty::Span;
+
+use super::verdict::Verdict;
+use crate::symex::unsupported::Unsupported;
+
+/// Results of verifying a crate. Printed by `--output-format json`.
+#[derive(Serialize)]
+pub struct Report {
+    #[serde(rename = "crate")]
+    pub crate_name: String,
+    pub result: Verdict,
+    pub harnesses: Vec<HarnessReport>,
+}
+
+#[derive(Serialize, Default)]
+pub struct HarnessReport {
+    pub harness: String,
+    pub result: Verdict,
+    pub properties: Vec<PropertyReport>,
+    pub statistics: Statistics,
+    /// Only exists if some property fails
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counterexample: Option<Vec<TraceStep>>,
+    /// Constructs that stop symex. The result is `unknown` if it is not empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unsupported: Vec<UnsupportedReport>,
+    /// Functions over-approximated by `--havoc`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub havocked: Vec<String>,
+    /// Rounds of `--incremental-unwind`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rounds: Vec<RoundReport>,
+    /// `rbmc::cover` and the reachability of `rbmc::assert`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub covers: Vec<CoverReport>,
+}
+
+#[derive(Serialize)]
+pub struct RoundReport {
+    /// The loop bound, or the `k` of the inductive step
+    pub unwind: usize,
+    pub unwinding_assertions: bool,
+    pub inductive_step: bool,
+    pub result: Verdict,
+}
+
+impl RoundReport {
+    pub fn label(&self) -> String {
+        if self.inductive_step {
+            format!("unwind {} inductive step", self.unwind)
+        } else if self.unwinding_assertions {
+            format!("unwind {} with unwinding assertions", self.unwind)
+        } else {
+            format!("unwind {}", self.unwind)
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PropertyReport {
+    pub message: String,
+    /// `success`, `fail` or `unknown`. Properties that are not checked
+    /// are `unknown`.
+    pub status: Verdict,
+    pub span: Option<SpanReport>,
+}
+
+#[derive(Serialize)]
+pub struct CoverReport {
+    /// `cover`, or `reach` for the reachability of an assertion
+    pub kind: String,
+    pub message: String,
+    /// `satisfied`, `unsatisfiable` or `unknown`. An unsatisfiable `reach`
+    /// is a dead assertion.
+    pub status: String,
+    pub span: Option<SpanReport>,
+}
+
+#[derive(Serialize)]
+pub struct SpanReport {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<Span> for SpanReport {
+    fn from(span: Span) -> Self {
+        let lines = span.get_lines();
+        SpanReport { file: span.get_filename(), line: lines.start_line, column: lines.start_col }
+    }
+}
+
+#[derive(Serialize)]
+pub struct UnsupportedReport {
+    pub construct: String,
+    pub function: Option<String>,
+    pub span: Option<SpanReport>,
+}
+
+impl From<&Unsupported> for UnsupportedReport {
+    fn from(unsupported: &Unsupported) -> Self {
+        UnsupportedReport {
+            construct: unsupported.construct.clone(),
+            function: unsupported.function.map(|function| function.to_string()),
+            span: unsupported.span.map(SpanReport::from),
+        }
+    }
+}
+
+/// Times are in seconds
+#[derive(Serialize, Default)]
+pub struct Statistics {
+    pub symex_steps: usize,
+    pub vccs: usize,
+    pub sliced_steps: usize,
+    pub symex_time: f32,
+    pub convert_time: f32,
+    pub solver_time: f32,
+    pub verification_time: f32,
+}
+
+#[derive(Serialize)]
+pub struct TraceStep {
+    pub function: String,
+    pub line: Option<usize>,
+    pub variable: String,
+    pub value: String,
+    /// The value is chosen by `rbmc::nondet`
+    pub nondet: bool,
+}
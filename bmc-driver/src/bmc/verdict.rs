@@ -0,0 +1,87 @@
+use std::fmt::Debug;
+
+use rust_bmc::exit_code;
+use serde::Serialize;
+
+use crate::solvers::solver::PResult;
+
+/// The verdict of verification. Verdicts are ordered by severity, and the
+/// verdict of a crate is the most severe one of its harnesses. Harnesses and
+/// properties are `unknown` until they are checked.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    /// No entry function is verified
+    Skipped,
+    Success,
+    #[default]
+    Unknown,
+    /// Symex reaches unsupported constructs. It is shown as `unknown`.
+    #[serde(rename = "unknown")]
+    Unsupported,
+    Fail,
+}
+
+impl Verdict {
+    pub fn exit_code(&self) -> u8 {
+        let code = match self {
+            Verdict::Skipped | Verdict::Success => exit_code::SUCCESS,
+            Verdict::Fail => exit_code::FAIL,
+            Verdict::Unknown => exit_code::UNKNOWN,
+            Verdict::Unsupported => exit_code::UNSUPPORTED,
+        };
+        code as u8
+    }
+}
+
+impl From<PResult> for Verdict {
+    fn from(res: PResult) -> Self {
+        match res {
+            PResult::PSat => Verdict::Fail,
+            PResult::PUnknow => Verdict::Unknown,
+            PResult::PUnsat => Verdict::Success,
+        }
+    }
+}
+
+/// The result of a cover. A cover is satisfiable if some path reaches it with
+/// its condition.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CoverResult {
+    Satisfied,
+    Unsatisfiable,
+    Unknown,
+}
+
+impl From<PResult> for CoverResult {
+    fn from(res: PResult) -> Self {
+        match res {
+            PResult::PSat => CoverResult::Satisfied,
+            PResult::PUnknow => CoverResult::Unknown,
+            PResult::PUnsat => CoverResult::Unsatisfiable,
+        }
+    }
+}
+
+impl Debug for CoverResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let result = match self {
+            CoverResult::Satisfied => "satisfied",
+            CoverResult::Unsatisfiable => "unsatisfiable",
+            CoverResult::Unknown => "unknown",
+        };
+        write!(f, "{result}")
+    }
+}
+
+impl Debug for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let verdict = match self {
+            Verdict::Skipped => "skipped",
+            Verdict::Success => "success",
+            Verdict::Unknown | Verdict::Unsupported => "unknown",
+            Verdict::Fail => "fail",
+        };
+        write!(f, "{verdict}")
+    }
+}
@@ -1,10 +1,9 @@
 use clap::*;
+use rust_bmc::{RBMC_CRATE, RBMC_FLAGS, RBMC_FLAGS_SEP};
 
+use super::unwind;
 use crate::symbol::nstring::NString;
 
-pub const RBMC_CRATE: &str = "RBMC_CRATE";
-pub const RBMC_FLAGS: &str = "RBMC_FLAGS";
-
 #[derive(clap::ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum DisplayState {
     #[default]
@@ -22,6 +21,20 @@ pub enum SmtStrategy {
     Once,
 }
 
+#[derive(clap::ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IntEncoding {
+    #[default]
+    Int,
+    Bv,
+}
+
+#[derive(clap::ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
 #[derive(Parser, Debug, Default)]
 #[command(version, about, long_about = None)]
 pub struct Cli {
@@ -29,14 +42,82 @@ pub struct Cli {
     #[arg(default_value_t = NString::EMPTY)]
     pub file: NString,
 
-    /// Entry function
-    #[arg(long, default_value_t = NString::from("main"))]
-    pub entry_function: NString,
+    /// Entry function. By default, all proof harnesses are verified, or 'main'
+    /// if there is no harness. With '--check-contracts', it is the function
+    /// whose contract is checked
+    #[arg(long)]
+    pub entry_function: Option<NString>,
 
     /// Loop bound. '0' indicates unbounded
     #[arg(long, default_value_t = 0)]
     pub unwind: usize,
 
+    /// Loop bounds in the form of 'TARGET=BOUND'. A target is a function 'F',
+    /// a loop 'F:bbN', or the loop at a source line 'FILE:LINE'. The bound of
+    /// a loop is the most specific one
+    #[arg(long, value_delimiter = ',', value_parser = unwind::check_spec)]
+    pub unwindset: Vec<String>,
+
+    /// A file of loop bounds in the form of '--unwindset', one per line
+    #[arg(long)]
+    pub unwindset_file: Option<NString>,
+
+    /// Check that loops can not run more iterations than the loop bound,
+    /// instead of assuming it
+    #[arg(long, default_value_t = false)]
+    pub unwinding_assertions: bool,
+
+    /// Check whether each 'rbmc::assert' is reachable, and report the dead ones
+    #[arg(long, default_value_t = false)]
+    pub assertion_reach_checks: bool,
+
+    /// The maximal number of frames of a recursive function on the call
+    /// stack. '0' indicates unbounded
+    #[arg(long, default_value_t = 0)]
+    pub recursion_bound: usize,
+
+    /// Recursion bounds of functions in the form of 'F=BOUND'
+    #[arg(long, value_delimiter = ',', value_parser = unwind::check_recursion_spec)]
+    pub recursion_boundset: Vec<String>,
+
+    /// The maximal length of the 'Vec's and slices in the values of
+    /// 'rbmc::nondet' and 'rbmc::Arbitrary::any'
+    #[arg(long, default_value_t = 4)]
+    pub nondet_len: usize,
+
+    /// Verify with the loop bounds 1, 2, ... until a property fails or
+    /// unwinding assertions show that the bound is enough
+    #[arg(long, default_value_t = false)]
+    pub incremental_unwind: bool,
+
+    /// Prove the properties for all loop iterations by k-induction with
+    /// k = 1, 2, ...
+    #[arg(long, default_value_t = false)]
+    pub k_induction: bool,
+
+    /// The last loop bound of '--incremental-unwind' and '--k-induction'
+    #[arg(long, default_value_t = 20)]
+    pub max_unwind: usize,
+
+    /// No more rounds of '--incremental-unwind' and '--k-induction' are
+    /// started after the time limit in seconds
+    #[arg(long)]
+    pub unwind_time_limit: Option<u64>,
+
+    /// Verify the functions with contracts against their contracts instead of
+    /// the entry functions. The arguments are nondet
+    #[arg(long, default_value_t = false)]
+    pub check_contracts: bool,
+
+    /// Replace calls to the functions with contracts by their contracts
+    #[arg(long, default_value_t = false)]
+    pub replace_contracts: bool,
+
+    /// Replace the function 'ORIGINAL' by a function 'REPLACEMENT' in the
+    /// local crate, in the form of 'ORIGINAL=REPLACEMENT'
+    #[arg(long, value_delimiter = ',', value_parser = check_stub)]
+    pub stub: Vec<String>,
+
     /// Show program
     #[arg(long, default_value_t = false)]
     pub show_program: bool,
@@ -66,6 +147,10 @@ pub struct Cli {
     #[arg(long, default_value_t = false)]
     pub no_slice: bool,
 
+    /// Do not check arithmetic overflow
+    #[arg(long, default_value_t = false)]
+    pub no_overflow_checks: bool,
+
     /// The strategy for invoking SMT solver.
     ///
     /// `Forward`: stop while an assertion fail.
@@ -75,6 +160,31 @@ pub struct Cli {
     #[arg(long, default_value_t = SmtStrategy::Once)]
     pub smt_strategy: SmtStrategy,
 
+    /// The encoding of Rust integers in SMT formula.
+    ///
+    /// `Int`: unbounded mathematical integers.
+    ///
+    /// `Bv`: bitvectors with the width of the integer type.
+    #[clap(value_enum)]
+    #[arg(long, default_value_t = IntEncoding::Int)]
+    pub int_encoding: IntEncoding,
+
+    /// The format of verification results.
+    ///
+    /// `Text`: messages for human.
+    ///
+    /// `Json`: one JSON document per run, including harnesses, properties,
+    /// statistics and counterexamples.
+    #[clap(value_enum)]
+    #[arg(long, default_value_t = OutputFormat::Text)]
+    pub output_format: OutputFormat,
+
+    /// Over-approximate calls to functions without bodies or models. The
+    /// return value and the pointees of mutable pointer arguments become
+    /// nondet.
+    #[arg(long, default_value_t = false)]
+    pub havoc: bool,
+
     /// Show SMT formula
     #[arg(long, default_value_t = false)]
     pub show_smt: bool,
@@ -92,6 +202,23 @@ pub struct Cli {
     pub show_warnings: bool,
 }
 
+/// Parse `ORIGINAL=REPLACEMENT` of `--stub`
+pub fn parse_stub(spec: &str) -> Result<(NString, NString), String> {
+    match spec.split_once('=') {
+        Some((original, replacement))
+            if !original.trim().is_empty() && !replacement.trim().is_empty() =>
+        {
+            Ok((original.trim().into(), replacement.trim().into()))
+        }
+        _ => Err(format!("Invalid stub {spec:?}, e.g., Instant::now=fake_now")),
+    }
+}
+
+/// Used by `clap` to check the arguments of `--stub`
+fn check_stub(spec: &str) -> Result<String, String> {
+    parse_stub(spec).map(|_| spec.to_string())
+}
+
 impl Cli {
     pub fn new() -> Self {
         // Carefully, dno't print anything in this function.
@@ -99,13 +226,14 @@ impl Cli {
         match std::env::var(RBMC_CRATE) {
             // From `cargo-rbmc` or `cargo rbmc`
             Ok(_crate) => {
-                let mirv_args = match std::env::var(RBMC_FLAGS) {
-                    Ok(flags) => {
-                        flags.split_whitespace().map(|arg| arg.to_string()).collect::<Vec<_>>()
+                let mut rbmc_args = vec![String::from("bmc-driver")];
+                match std::env::var(RBMC_FLAGS) {
+                    Ok(flags) if !flags.is_empty() => {
+                        rbmc_args.extend(flags.split(RBMC_FLAGS_SEP).map(String::from))
                     }
-                    _ => vec![],
-                };
-                Cli::parse_from(mirv_args)
+                    _ => {}
+                }
+                Cli::parse_from(rbmc_args)
             }
             // From `rbmc *.rs`
             Err(_) => Cli::parse(),
@@ -114,8 +242,12 @@ impl Cli {
 
     pub fn rustc_args(&self) -> Vec<String> {
         let mut args = vec![std::env::current_exe().unwrap().to_str().unwrap().into()];
-        // TODO: fix cargo rbmc
-        args.push(self.file.to_string());
+        if std::env::var_os(RBMC_CRATE).is_some() {
+            // Arguments of rustc from `cargo`
+            args.extend(std::env::args().skip(1));
+        } else {
+            args.push(self.file.to_string());
+        }
         let extra_args = std::env::var("RUSTC_ARGS")
             .unwrap()
             .split(' ')
@@ -1,6 +1,9 @@
 use std::cell::RefCell;
 
+use stable_mir::ty::Span;
+
 use super::cli::*;
+use super::unwind::{Unwind, UnwindSet};
 use crate::expr::context::*;
 use crate::program::program::*;
 use crate::solvers::context::SolverCtx;
@@ -11,12 +14,15 @@ pub struct Config {
     pub(crate) program: Program,
     pub(crate) expr_ctx: ExprCtx,
     pub(crate) solver_config: SolverCtx,
+    pub(crate) unwind_set: UnwindSet,
 }
 
 impl Config {
-    pub fn new(cli: Cli) -> Self {
+    /// Errors in the options, e.g., a malformed `--unwindset-file` or a stub
+    /// not matching the signature, are returned as messages for users.
+    pub fn new(cli: Cli) -> Result<Self, String> {
         // Get stable mir
-        let program = Program::new(stable_mir::local_crate(), cli.entry_function);
+        let program = Program::new(stable_mir::local_crate(), &cli.stub)?;
 
         // Context for managing Expr
         let expr_ctx = ExprCtx::new(RefCell::new(Context::new()));
@@ -24,7 +30,76 @@ impl Config {
         // Initilized solver
         let solver_config = SolverCtx::new(&cli);
 
-        Config { cli, program, expr_ctx, solver_config }
+        let unwind_set =
+            UnwindSet::new(&cli.unwindset, cli.unwindset_file, &cli.recursion_boundset)?;
+
+        Ok(Config { cli, program, expr_ctx, solver_config, unwind_set })
+    }
+
+    /// The functions verified in turn. The entry function from the command line
+    /// is preferred, then the proof harnesses, and finally `main`. With
+    /// `--check-contracts`, they are the functions checking contracts, and the
+    /// entry function selects one of them.
+    pub fn entry_functions(&self) -> Vec<NString> {
+        if self.cli.check_contracts {
+            return self
+                .program
+                .contracts()
+                .iter()
+                .filter(|&&function| self.cli.entry_function.is_none_or(|f| f == function))
+                .map(|&function| contract_wrapper(function, ContractMode::Check))
+                .collect();
+        }
+        let entries = match self.cli.entry_function {
+            Some(entry) => vec![entry],
+            None if !self.program.harnesses().is_empty() => self.program.harnesses().clone(),
+            None => vec![NString::from("main")],
+        };
+        entries
+            .into_iter()
+            .filter(|&entry| {
+                self.program.contains_function(entry) && self.program.is_local_function(entry)
+            })
+            .collect()
+    }
+
+    /// The default bounds from the command line
+    pub fn unwind(&self) -> Unwind {
+        Unwind {
+            bound: self.cli.unwind,
+            recursion_bound: self.cli.recursion_bound,
+            assertions: self.cli.unwinding_assertions,
+        }
+    }
+
+    /// The bound of a loop in `function` reached by the harness `entry`. The most
+    /// specific one is chosen from the bounds of the loop, the function, the
+    /// harness and the default bound `unwind`. '0' indicates unbounded.
+    pub fn loop_bound(
+        &self,
+        entry: NString,
+        function: NString,
+        pc: usize,
+        span: Span,
+        unwind: usize,
+    ) -> usize {
+        self.unwind_set
+            .loop_bound(function, pc, span)
+            .or_else(|| self.unwind_set.function_bound(function))
+            .or_else(|| self.program.unwind_attr(function))
+            .or_else(|| self.program.unwind_attr(entry))
+            .unwrap_or(unwind)
+    }
+
+    /// The maximal number of frames of `function` on the call stack. '0'
+    /// indicates unbounded.
+    pub fn recursion_bound(&self, function: NString, recursion_bound: usize) -> usize {
+        self.unwind_set.recursion_bound(function).unwrap_or(recursion_bound)
+    }
+
+    /// Progress messages are only shown for text output
+    pub fn is_text_output(&self) -> bool {
+        self.cli.output_format == OutputFormat::Text
     }
 
     pub fn enable_display_state(&self) -> bool {
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use stable_mir::ty::Span;
+
+use crate::symbol::nstring::NString;
+
+/// The default bounds of loops and recursions, and whether they are checked
+/// by unwinding assertions. They come from the command line or from a round
+/// of `--incremental-unwind`.
+#[derive(Clone, Copy)]
+pub struct Unwind {
+    pub bound: usize,
+    pub recursion_bound: usize,
+    pub assertions: bool,
+}
+
+/// A target of `--unwindset`
+enum UnwindTarget {
+    /// All loops in a function, e.g., `foo`
+    Function(NString),
+    /// A loop in a function, e.g., `foo:bb2`
+    Loop(NString, usize),
+    /// The loop at a source line, e.g., `lib.rs:12`
+    Line(String, usize),
+}
+
+/// Parse `TARGET=BOUND`
+fn parse(spec: &str) -> Result<(UnwindTarget, usize), String> {
+    let (target, bound) =
+        spec.rsplit_once('=').ok_or(format!("Missing bound in {spec:?}, e.g., main:bb2=10"))?;
+    let bound = bound.trim().parse::<usize>().map_err(|_| format!("Invalid bound in {spec:?}"))?;
+    let target = target.trim();
+    let target = match target.rsplit_once(':') {
+        Some((function, bb))
+            if bb.strip_prefix("bb").is_some_and(|pc| pc.parse::<usize>().is_ok()) =>
+        {
+            UnwindTarget::Loop(function.into(), bb[2..].parse().unwrap())
+        }
+        Some((file, line)) if line.parse::<usize>().is_ok() => {
+            UnwindTarget::Line(file.into(), line.parse().unwrap())
+        }
+        _ => UnwindTarget::Function(target.into()),
+    };
+    Ok((target, bound))
+}
+
+/// Used by `clap` to check the arguments of `--unwindset`
+pub fn check_spec(spec: &str) -> Result<String, String> {
+    parse(spec).map(|_| spec.to_string())
+}
+
+/// Parse `F=BOUND` of `--recursion-boundset`
+fn parse_recursion(spec: &str) -> Result<(NString, usize), String> {
+    let (function, bound) =
+        spec.rsplit_once('=').ok_or(format!("Missing bound in {spec:?}, e.g., fib=10"))?;
+    let bound = bound.trim().parse::<usize>().map_err(|_| format!("Invalid bound in {spec:?}"))?;
+    Ok((function.trim().into(), bound))
+}
+
+/// Used by `clap` to check the arguments of `--recursion-boundset`
+pub fn check_recursion_spec(spec: &str) -> Result<String, String> {
+    parse_recursion(spec).map(|_| spec.to_string())
+}
+
+/// Loop bounds from `--unwindset` and `--unwindset-file`.
+#[derive(Default)]
+pub struct UnwindSet {
+    functions: HashMap<NString, usize>,
+    loops: HashMap<(NString, usize), usize>,
+    /// The file is a suffix of the file name in spans
+    lines: Vec<(String, usize, usize)>,
+    /// Recursion bounds from `--recursion-boundset`
+    recursions: HashMap<NString, usize>,
+}
+
+impl UnwindSet {
+    /// The specifications in the file are one per line. Lines starting with
+    /// `#` are comments. The specifications from the command line are added
+    /// after the file, so that they override the file.
+    pub fn new(
+        specs: &[String],
+        file: Option<NString>,
+        recursion_specs: &[String],
+    ) -> Result<Self, String> {
+        let mut unwind_set = UnwindSet::default();
+        if let Some(file) = file {
+            let content = std::fs::read_to_string(file.to_string())
+                .map_err(|e| format!("Fail to read the unwind file {file:?}: {e}"))?;
+            for (i, line) in content.lines().map(str::trim).enumerate() {
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                unwind_set.add(line).map_err(|e| format!("{file:?}:{}: {e}", i + 1))?;
+            }
+        }
+        for spec in specs {
+            unwind_set.add(spec)?;
+        }
+        for spec in recursion_specs {
+            let (function, bound) = parse_recursion(spec)?;
+            unwind_set.recursions.insert(function, bound);
+        }
+        Ok(unwind_set)
+    }
+
+    fn add(&mut self, spec: &str) -> Result<(), String> {
+        let (target, bound) = parse(spec)?;
+        match target {
+            UnwindTarget::Function(function) => {
+                self.functions.insert(function, bound);
+            }
+            UnwindTarget::Loop(function, pc) => {
+                self.loops.insert((function, pc), bound);
+            }
+            UnwindTarget::Line(file, line) => self.lines.insert(0, (file, line, bound)),
+        }
+        Ok(())
+    }
+
+    /// The bound of the loop whose head is `pc` and whose back-edge is at `span`
+    pub fn loop_bound(&self, function: NString, pc: usize, span: Span) -> Option<usize> {
+        if let Some(&bound) = self.loops.get(&(function, pc)) {
+            return Some(bound);
+        }
+        let filename = span.get_filename();
+        let line = span.get_lines().start_line;
+        self.lines
+            .iter()
+            .find(|(file, l, _)| *l == line && filename.ends_with(file.as_str()))
+            .map(|&(_, _, bound)| bound)
+    }
+
+    pub fn function_bound(&self, function: NString) -> Option<usize> {
+        self.functions.get(&function).copied()
+    }
+
+    pub fn recursion_bound(&self, function: NString) -> Option<usize> {
+        self.recursions.get(&function).copied()
+    }
+}
@@ -91,6 +91,9 @@ pub(super) enum NodeKind {
     Ite(NodeId, NodeId, NodeId),
     /// Type casting
     Cast(NodeId, NodeId),
+    /// `Overflow(op, lhs, rhs)`: the result of `lhs op rhs` is out of the bounds
+    /// of the operand type.
+    Overflow(BinOp, NodeId, NodeId),
 
     /// Unified wrapper for objects, including array, slice,
     /// struct, tuple, and so on. Moreover, heap objects and
@@ -183,6 +186,10 @@ impl NodeKind {
         matches!(self, NodeKind::Cast(..))
     }
 
+    pub fn is_overflow(&self) -> bool {
+        matches!(self, NodeKind::Overflow(..))
+    }
+
     pub fn is_object(&self) -> bool {
         matches!(self, NodeKind::Object(..))
     }
@@ -284,7 +291,10 @@ impl Node {
         match &self.kind {
             NodeKind::AddressOf(p) => vec![*p],
             NodeKind::Aggregate(nodes) => nodes.clone(),
-            NodeKind::Binary(_, l, r) | NodeKind::Cast(l, r) | NodeKind::SameObject(l, r) => {
+            NodeKind::Binary(_, l, r)
+            | NodeKind::Cast(l, r)
+            | NodeKind::Overflow(_, l, r)
+            | NodeKind::SameObject(l, r) => {
                 vec![*l, *r]
             }
             NodeKind::Unary(_, o) | NodeKind::Object(o) => vec![*o],
@@ -5,6 +5,7 @@ use std::fmt::Debug;
 use std::rc::Rc;
 
 use num_bigint::BigInt;
+use stable_mir::mir::Mutability;
 
 use super::ast::*;
 use super::constant::*;
@@ -176,6 +177,11 @@ impl Context {
         self.nodes[i].kind().is_cast()
     }
 
+    pub fn is_overflow(&self, i: NodeId) -> bool {
+        assert!(i < self.nodes.len());
+        self.nodes[i].kind().is_overflow()
+    }
+
     pub fn is_object(&self, i: NodeId) -> bool {
         assert!(i < self.nodes.len());
         self.nodes[i].kind().is_object()
@@ -306,7 +312,7 @@ impl Context {
     pub fn extract_bin_op(&self, i: NodeId) -> Result<BinOp, &str> {
         assert!(i < self.nodes.len());
         match self.nodes[i].kind() {
-            NodeKind::Binary(op, _, _) => Ok(*op),
+            NodeKind::Binary(op, _, _) | NodeKind::Overflow(op, _, _) => Ok(*op),
             _ => Err("Not binary operator"),
         }
     }
@@ -514,6 +520,71 @@ impl ExprBuilder for ExprCtx {
         Expr { ctx: self.clone(), id }
     }
 
+    fn rem(&self, lhs: Expr, rhs: Expr) -> Expr {
+        assert!(lhs.ty().is_integer() && rhs.ty().is_integer());
+        let kind = NodeKind::Binary(BinOp::Rem, lhs.id, rhs.id);
+        let ty = lhs.ty();
+        let new_node = Node::new(kind, ty);
+        let id = self.borrow_mut().add_node(new_node);
+        Expr { ctx: self.clone(), id }
+    }
+
+    fn bit_and(&self, lhs: Expr, rhs: Expr) -> Expr {
+        assert!(lhs.ty().is_integer() && lhs.ty() == rhs.ty());
+        let kind = NodeKind::Binary(BinOp::BitAnd, lhs.id, rhs.id);
+        let ty = lhs.ty();
+        let new_node = Node::new(kind, ty);
+        let id = self.borrow_mut().add_node(new_node);
+        Expr { ctx: self.clone(), id }
+    }
+
+    fn bit_or(&self, lhs: Expr, rhs: Expr) -> Expr {
+        assert!(lhs.ty().is_integer() && lhs.ty() == rhs.ty());
+        let kind = NodeKind::Binary(BinOp::BitOr, lhs.id, rhs.id);
+        let ty = lhs.ty();
+        let new_node = Node::new(kind, ty);
+        let id = self.borrow_mut().add_node(new_node);
+        Expr { ctx: self.clone(), id }
+    }
+
+    fn bit_xor(&self, lhs: Expr, rhs: Expr) -> Expr {
+        assert!(lhs.ty().is_integer() && lhs.ty() == rhs.ty());
+        let kind = NodeKind::Binary(BinOp::BitXor, lhs.id, rhs.id);
+        let ty = lhs.ty();
+        let new_node = Node::new(kind, ty);
+        let id = self.borrow_mut().add_node(new_node);
+        Expr { ctx: self.clone(), id }
+    }
+
+    fn shl(&self, lhs: Expr, rhs: Expr) -> Expr {
+        // The shift amount may have a different integer type
+        assert!(lhs.ty().is_integer() && rhs.ty().is_integer());
+        let kind = NodeKind::Binary(BinOp::Shl, lhs.id, rhs.id);
+        let ty = lhs.ty();
+        let new_node = Node::new(kind, ty);
+        let id = self.borrow_mut().add_node(new_node);
+        Expr { ctx: self.clone(), id }
+    }
+
+    fn shr(&self, lhs: Expr, rhs: Expr) -> Expr {
+        // The shift amount may have a different integer type
+        assert!(lhs.ty().is_integer() && rhs.ty().is_integer());
+        let kind = NodeKind::Binary(BinOp::Shr, lhs.id, rhs.id);
+        let ty = lhs.ty();
+        let new_node = Node::new(kind, ty);
+        let id = self.borrow_mut().add_node(new_node);
+        Expr { ctx: self.clone(), id }
+    }
+
+    fn cmp(&self, lhs: Expr, rhs: Expr, ty: Type) -> Expr {
+        assert!(lhs.ty() == rhs.ty());
+        assert!(ty.is_enum());
+        let kind = NodeKind::Binary(BinOp::Cmp, lhs.id, rhs.id);
+        let new_node = Node::new(kind, ty);
+        let id = self.borrow_mut().add_node(new_node);
+        Expr { ctx: self.clone(), id }
+    }
+
     fn eq(&self, lhs: Expr, rhs: Expr) -> Expr {
         assert!(lhs.ty() == rhs.ty());
         let kind = NodeKind::Binary(BinOp::Eq, lhs.id, rhs.id);
@@ -644,6 +715,16 @@ impl ExprBuilder for ExprCtx {
         Expr { ctx: self.clone(), id }
     }
 
+    fn overflow(&self, op: BinOp, lhs: Expr, rhs: Expr) -> Expr {
+        assert!(matches!(op, BinOp::Add | BinOp::Sub | BinOp::Mul));
+        assert!(lhs.ty().is_integer() && lhs.ty() == rhs.ty());
+        let kind = NodeKind::Overflow(op, lhs.id, rhs.id);
+        let ty = Type::bool_type();
+        let new_node = Node::new(kind, ty);
+        let id = self.borrow_mut().add_node(new_node);
+        Expr { ctx: self.clone(), id }
+    }
+
     fn object(&self, inner_expr: Expr) -> Expr {
         assert!(!inner_expr.is_object());
         let kind = NodeKind::Object(inner_expr.id);
@@ -792,6 +873,55 @@ impl ExprBuilder for ExprCtx {
         self.unique_raw(unique)
     }
 
+    /// To construct a `Vec` from its buffer `*const [T; 0]`, length and capacity.
+    /// The layout is `Vec { buf: RawVec { inner: RawVecInner { ptr, cap, alloc }, .. }, len }`,
+    /// where the buffer is kept in `ptr: Unique<u8>`.
+    fn _vec(&self, pt: Expr, len: Expr, cap: Expr, ty: Type) -> Expr {
+        assert!(pt.ty().is_const_ptr() && ty.is_vec());
+        let raw_vec_ty = ty.field_type(0);
+        let inner_ty = raw_vec_ty.field_type(0);
+        let unique = self.unique(pt, inner_ty.field_type(0));
+        let cap = self.aggregate(vec![cap], inner_ty.field_type(1));
+        let alloc = self.constant_zst(inner_ty.field_type(2));
+        let inner = self.aggregate(vec![unique, cap, alloc], inner_ty);
+        let marker = self.constant_zst(raw_vec_ty.field_type(1));
+        let raw_vec = self.aggregate(vec![inner, marker], raw_vec_ty);
+        self.aggregate(vec![raw_vec, len], ty)
+    }
+
+    /// Retrieve the buffer `*const [T; 0]` inside a `Vec`
+    fn vec_raw(&self, _vec: Expr) -> Expr {
+        assert!(_vec.ty().is_vec());
+        let i = self.constant_usize(0);
+        let raw_vec_ty = _vec.ty().field_type(0);
+        let raw_vec = self.index(_vec.clone(), i.clone(), raw_vec_ty);
+        let inner_ty = raw_vec_ty.field_type(0);
+        let inner = self.index(raw_vec, i.clone(), inner_ty);
+        let unique = self.index(inner, i, inner_ty.field_type(0));
+        let buffer_ty = Type::infinite_array_type(_vec.ty().vec_elem_type());
+        let ty = Type::ptr_type(buffer_ty, Mutability::Not);
+        self.cast(self.unique_raw(unique), self.mk_type(ty))
+    }
+
+    /// Retrieve the length of a `Vec`
+    fn vec_len(&self, _vec: Expr) -> Expr {
+        assert!(_vec.ty().is_vec());
+        self.index(_vec, self.constant_usize(1), Type::usize_type())
+    }
+
+    /// Retrieve the capacity of a `Vec`
+    fn vec_cap(&self, _vec: Expr) -> Expr {
+        assert!(_vec.ty().is_vec());
+        let i = self.constant_usize(0);
+        let raw_vec_ty = _vec.ty().field_type(0);
+        let raw_vec = self.index(_vec, i.clone(), raw_vec_ty);
+        let inner_ty = raw_vec_ty.field_type(0);
+        let inner = self.index(raw_vec, i, inner_ty);
+        let cap_ty = inner_ty.field_type(1);
+        let cap = self.index(inner, self.constant_usize(1), cap_ty);
+        self.index(cap, self.constant_usize(0), Type::usize_type())
+    }
+
     fn variant(&self, idx: Expr, data: Expr, ty: Type) -> Expr {
         assert!(ty.is_enum() && !data.ty().is_zero_sized_type());
         let i = bigint_to_usize(&idx.extract_constant().to_integer());
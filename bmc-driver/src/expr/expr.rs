@@ -99,6 +99,10 @@ impl Expr {
         self.ctx.borrow().is_cast(self.id)
     }
 
+    pub fn is_overflow(&self) -> bool {
+        self.ctx.borrow().is_overflow(self.id)
+    }
+
     pub fn is_object(&self) -> bool {
         self.ctx.borrow().is_object(self.id)
     }
@@ -239,17 +243,17 @@ impl Expr {
     }
 
     pub fn extract_bin_op(&self) -> BinOp {
-        assert!(self.is_binary());
+        assert!(self.is_binary() || self.is_overflow());
         self.ctx.borrow().extract_bin_op(self.id).unwrap()
     }
 
     pub fn extract_lhs(&self) -> Expr {
-        assert!(self.is_binary() || self.is_same_object());
+        assert!(self.is_binary() || self.is_same_object() || self.is_overflow());
         self.extract_sub_expr(0)
     }
 
     pub fn extract_rhs(&self) -> Expr {
-        assert!(self.is_binary() || self.is_same_object());
+        assert!(self.is_binary() || self.is_same_object() || self.is_overflow());
         self.extract_sub_expr(1)
     }
 
@@ -450,6 +454,13 @@ impl Expr {
                 BinOp::Sub => self.ctx.sub(lhs, rhs),
                 BinOp::Mul => self.ctx.mul(lhs, rhs),
                 BinOp::Div => self.ctx.div(lhs, rhs),
+                BinOp::Rem => self.ctx.rem(lhs, rhs),
+                BinOp::BitAnd => self.ctx.bit_and(lhs, rhs),
+                BinOp::BitOr => self.ctx.bit_or(lhs, rhs),
+                BinOp::BitXor => self.ctx.bit_xor(lhs, rhs),
+                BinOp::Shl => self.ctx.shl(lhs, rhs),
+                BinOp::Shr => self.ctx.shr(lhs, rhs),
+                BinOp::Cmp => self.ctx.cmp(lhs, rhs, self.ty()),
                 BinOp::Eq => self.ctx.eq(lhs, rhs),
                 BinOp::Ne => self.ctx.ne(lhs, rhs),
                 BinOp::Ge => self.ctx.ge(lhs, rhs),
@@ -481,6 +492,13 @@ impl Expr {
             return;
         }
 
+        if self.is_overflow() {
+            let lhs = sub_exprs[0].clone();
+            let rhs = sub_exprs[1].clone();
+            *self = self.ctx.overflow(self.extract_bin_op(), lhs, rhs);
+            return;
+        }
+
         if self.is_object() {
             let inner_expr = sub_exprs[0].clone();
             *self = self.ctx.object(inner_expr);
@@ -635,6 +653,12 @@ impl Debug for Expr {
                 return write!(f, "{lhs:?} as {ty:?}");
             }
 
+            if self.is_overflow() {
+                let lhs = &sub_exprs[0];
+                let rhs = &sub_exprs[1];
+                return write!(f, "overflow({lhs:?} {:?} {rhs:?})", self.extract_bin_op());
+            }
+
             if self.is_object() {
                 return write!(f, "{:?}", sub_exprs[0]);
             }
@@ -782,6 +806,14 @@ pub trait ExprBuilder {
     fn sub(&self, lhs: Expr, rhs: Expr) -> Expr;
     fn mul(&self, lhs: Expr, rhs: Expr) -> Expr;
     fn div(&self, lhs: Expr, rhs: Expr) -> Expr;
+    fn rem(&self, lhs: Expr, rhs: Expr) -> Expr;
+    fn bit_and(&self, lhs: Expr, rhs: Expr) -> Expr;
+    fn bit_or(&self, lhs: Expr, rhs: Expr) -> Expr;
+    fn bit_xor(&self, lhs: Expr, rhs: Expr) -> Expr;
+    fn shl(&self, lhs: Expr, rhs: Expr) -> Expr;
+    fn shr(&self, lhs: Expr, rhs: Expr) -> Expr;
+    /// Three-way comparison. `ty` is `core::cmp::Ordering`.
+    fn cmp(&self, lhs: Expr, rhs: Expr, ty: Type) -> Expr;
     fn eq(&self, lhs: Expr, rhs: Expr) -> Expr;
     fn ne(&self, lhs: Expr, rhs: Expr) -> Expr;
     fn ge(&self, lhs: Expr, rhs: Expr) -> Expr;
@@ -796,6 +828,7 @@ pub trait ExprBuilder {
     fn neg(&self, operand: Expr) -> Expr;
     fn ite(&self, cond: Expr, true_value: Expr, false_value: Expr) -> Expr;
     fn cast(&self, operand: Expr, target_ty: Expr) -> Expr;
+    fn overflow(&self, op: BinOp, lhs: Expr, rhs: Expr) -> Expr;
 
     fn object(&self, inner_expr: Expr) -> Expr;
     fn slice(&self, object: Expr, start: Expr, len: Expr) -> Expr;
@@ -813,6 +846,10 @@ pub trait ExprBuilder {
     fn unique_raw(&self, unique: Expr) -> Expr;
     fn _box(&self, pt: Expr) -> Expr;
     fn box_raw(&self, _box: Expr) -> Expr;
+    fn _vec(&self, pt: Expr, len: Expr, cap: Expr, ty: Type) -> Expr;
+    fn vec_raw(&self, _vec: Expr) -> Expr;
+    fn vec_len(&self, _vec: Expr) -> Expr;
+    fn vec_cap(&self, _vec: Expr) -> Expr;
 
     fn variant(&self, idx: Expr, data: Expr, ty: Type) -> Expr;
     fn as_variant(&self, x: Expr, idx: Expr) -> Expr;
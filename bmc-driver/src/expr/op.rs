@@ -8,6 +8,13 @@ pub enum BinOp {
     Sub,
     Mul,
     Div,
+    Rem,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+    Cmp,
     Eq,
     Ne,
     Ge,
@@ -27,6 +34,13 @@ impl Debug for BinOp {
             BinOp::Sub => write!(f, "-"),
             BinOp::Mul => write!(f, "*"),
             BinOp::Div => write!(f, "/"),
+            BinOp::Rem => write!(f, "%"),
+            BinOp::BitAnd => write!(f, "&"),
+            BinOp::BitOr => write!(f, "|"),
+            BinOp::BitXor => write!(f, "^"),
+            BinOp::Shl => write!(f, "<<"),
+            BinOp::Shr => write!(f, ">>"),
+            BinOp::Cmp => write!(f, "<=>"),
             BinOp::Eq => write!(f, "=="),
             BinOp::Ne => write!(f, "!="),
             BinOp::Ge => write!(f, ">="),
@@ -48,16 +62,20 @@ impl From<mir::BinOp> for BinOp {
             mir::BinOp::Sub | mir::BinOp::SubUnchecked => BinOp::Sub,
             mir::BinOp::Mul | mir::BinOp::MulUnchecked => BinOp::Mul,
             mir::BinOp::Div => BinOp::Div,
+            mir::BinOp::Rem => BinOp::Rem,
+            mir::BinOp::BitAnd => BinOp::BitAnd,
+            mir::BinOp::BitOr => BinOp::BitOr,
+            mir::BinOp::BitXor => BinOp::BitXor,
+            mir::BinOp::Shl | mir::BinOp::ShlUnchecked => BinOp::Shl,
+            mir::BinOp::Shr | mir::BinOp::ShrUnchecked => BinOp::Shr,
+            mir::BinOp::Cmp => BinOp::Cmp,
             mir::BinOp::Eq => BinOp::Eq,
             mir::BinOp::Ne => BinOp::Ne,
             mir::BinOp::Le => BinOp::Le,
             mir::BinOp::Lt => BinOp::Lt,
             mir::BinOp::Ge => BinOp::Ge,
             mir::BinOp::Gt => BinOp::Gt,
-            mir::BinOp::BitAnd => BinOp::And,
-            mir::BinOp::BitOr => BinOp::Or,
             mir::BinOp::Offset => BinOp::Offset,
-            _ => todo!("{value:?}"),
         }
     }
 }
@@ -28,6 +28,8 @@ impl Expr {
             self.simplify_ite();
         } else if self.is_cast() {
             self.simplify_cast();
+        } else if self.is_overflow() {
+            self.simplify_overflow();
         } else if self.is_object() {
             let mut inner_expr = self.extract_inner_expr();
             if inner_expr.simplify() {
@@ -84,7 +86,7 @@ impl Expr {
                     BinOp::Lt => self.ctx.ge(lhs, rhs),
                     BinOp::And => self.ctx.or(lhs, rhs),
                     BinOp::Or => self.ctx.and(lhs, rhs),
-                    _ => panic!("Impossible"),
+                    _ => unreachable!(),
                 };
             } else {
                 *self = match self.extract_bin_op() {
@@ -96,14 +98,14 @@ impl Expr {
                     BinOp::Lt => self.ctx.lt(lhs, rhs),
                     BinOp::And => self.ctx.and(lhs, rhs),
                     BinOp::Or => self.ctx.or(lhs, rhs),
-                    _ => panic!("Impossible"),
+                    _ => unreachable!(),
                 };
             }
         } else if self.is_unary() {
             let mut operand = self.extract_inner_expr();
             match self.extract_un_op() {
                 UnOp::Not => operand.to_nnf(!is_not),
-                _ => panic!("Impossible"),
+                _ => unreachable!(),
             };
             *self = operand;
         } else if is_not {
@@ -133,7 +135,11 @@ impl Expr {
 
     fn simplify_binary(&mut self) {
         match self.extract_bin_op() {
-            BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div => self.simplify_arith(),
+            BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Rem => self.simplify_arith(),
+            BinOp::BitAnd | BinOp::BitOr | BinOp::BitXor | BinOp::Shl | BinOp::Shr => {
+                self.simplify_bitwise()
+            }
+            BinOp::Cmp => self.simplify_three_way_cmp(),
             BinOp::Eq | BinOp::Ne | BinOp::Ge | BinOp::Gt | BinOp::Le | BinOp::Lt => {
                 self.simplify_cmp()
             }
@@ -146,7 +152,22 @@ impl Expr {
         let (changed, args) = self.simplifid_args();
         let lhs = args[0].clone();
         let rhs = args[1].clone();
-        if lhs.is_constant() && rhs.is_constant() {
+        if rhs.is_constant() && rhs.extract_constant().to_integer() == BigInt::ZERO {
+            let mut res = match self.extract_bin_op() {
+                BinOp::Add | BinOp::Sub => lhs,
+                BinOp::Mul => self.ctx.constant_integer(BigInt::ZERO, self.ty()),
+                BinOp::Div | BinOp::Rem => {
+                    // Dividing by zero is claimed as an error during symex. Keep it as it is.
+                    if changed {
+                        self.replace_sub_exprs(vec![lhs, rhs]);
+                    }
+                    return;
+                }
+                _ => unreachable!(),
+            };
+            res.simplify();
+            *self = res;
+        } else if lhs.is_constant() && rhs.is_constant() {
             let a = lhs.extract_constant().to_integer();
             let b = rhs.extract_constant().to_integer();
             let res = match self.extract_bin_op() {
@@ -154,24 +175,18 @@ impl Expr {
                 BinOp::Sub => a - b,
                 BinOp::Mul => a * b,
                 BinOp::Div => a / b,
-                _ => todo!("Impossible"),
+                BinOp::Rem => a % b,
+                _ => unreachable!(),
             };
             *self = self.ctx.constant_integer(res, self.ty());
         } else if lhs.is_constant() && lhs.extract_constant().to_integer() == BigInt::ZERO {
             let mut res = match self.extract_bin_op() {
                 BinOp::Add => rhs,
                 BinOp::Sub => self.ctx.neg(rhs),
-                BinOp::Mul | BinOp::Div => self.ctx.constant_integer(BigInt::ZERO, self.ty()),
-                _ => todo!("Impossible"),
-            };
-            res.simplify();
-            *self = res;
-        } else if rhs.is_constant() && rhs.extract_constant().to_integer() == BigInt::ZERO {
-            let mut res = match self.extract_bin_op() {
-                BinOp::Add | BinOp::Sub => lhs,
-                BinOp::Mul => self.ctx.constant_integer(BigInt::ZERO, self.ty()),
-                BinOp::Div => panic!("Div zero"),
-                _ => todo!("Impossible"),
+                BinOp::Mul | BinOp::Div | BinOp::Rem => {
+                    self.ctx.constant_integer(BigInt::ZERO, self.ty())
+                }
+                _ => unreachable!(),
             };
             res.simplify();
             *self = res;
@@ -182,8 +197,76 @@ impl Expr {
                 BinOp::Sub => self.ctx.sub(lhs, rhs),
                 BinOp::Mul => self.ctx.mul(lhs, rhs),
                 BinOp::Div => self.ctx.div(lhs, rhs),
-                _ => todo!("Impossible"),
+                BinOp::Rem => self.ctx.rem(lhs, rhs),
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    fn simplify_bitwise(&mut self) {
+        let (changed, args) = self.simplifid_args();
+        let lhs = args[0].clone();
+        let rhs = args[1].clone();
+        if lhs.is_constant() && rhs.is_constant() {
+            let ty = self.ty();
+            // Operands and the result are wrapped to the width and the signedness of
+            // the type, as the bitvector encoding does.
+            let a = ty.wrap_integer(lhs.extract_constant().to_integer());
+            let b = rhs.extract_constant().to_integer();
+            // The shift amount is masked by the bit width, as MIR does.
+            let bits = BigInt::from(ty.size() * 8);
+            let n = || bigint_to_usize(&(((b.clone() % &bits) + &bits) % &bits));
+            let res = match self.extract_bin_op() {
+                BinOp::BitAnd => a & ty.wrap_integer(b.clone()),
+                BinOp::BitOr => a | ty.wrap_integer(b.clone()),
+                BinOp::BitXor => a ^ ty.wrap_integer(b.clone()),
+                BinOp::Shl => a << n(),
+                BinOp::Shr => a >> n(),
+                _ => unreachable!(),
             };
+            *self = self.ctx.constant_integer(ty.wrap_integer(res), ty);
+        } else if changed {
+            self.replace_sub_exprs(vec![lhs, rhs]);
+        }
+    }
+
+    fn simplify_three_way_cmp(&mut self) {
+        let (changed, args) = self.simplifid_args();
+        let lhs = args[0].clone();
+        let rhs = args[1].clone();
+        if lhs.is_constant() && rhs.is_constant() {
+            let a = lhs.extract_constant().to_integer();
+            let b = rhs.extract_constant().to_integer();
+            // Variants of `Ordering` are `Less`, `Equal` and `Greater`
+            let idx = match a.cmp(&b) {
+                std::cmp::Ordering::Less => 0,
+                std::cmp::Ordering::Equal => 1,
+                std::cmp::Ordering::Greater => 2,
+            };
+            let idx = self.ctx.constant_usize(idx).extract_constant();
+            *self = self.ctx.constant_adt(vec![idx], self.ty());
+        } else if changed {
+            self.replace_sub_exprs(vec![lhs, rhs]);
+        }
+    }
+
+    fn simplify_overflow(&mut self) {
+        let (changed, args) = self.simplifid_args();
+        let lhs = args[0].clone();
+        let rhs = args[1].clone();
+        if lhs.is_constant() && rhs.is_constant() {
+            let a = lhs.extract_constant().to_integer();
+            let b = rhs.extract_constant().to_integer();
+            let res = match self.extract_bin_op() {
+                BinOp::Add => a + b,
+                BinOp::Sub => a - b,
+                BinOp::Mul => a * b,
+                _ => unreachable!(),
+            };
+            let ty = lhs.ty();
+            *self = self.ctx.constant_bool(res < ty.min_value() || res > ty.max_value());
+        } else if changed {
+            *self = self.ctx.overflow(self.extract_bin_op(), lhs, rhs);
         }
     }
 
@@ -202,7 +285,7 @@ impl Expr {
                     BinOp::Gt => a > b,
                     BinOp::Le => a <= b,
                     BinOp::Lt => a < b,
-                    _ => todo!("Impossible"),
+                    _ => unreachable!(),
                 }
             } else if lhs.ty().is_bool() {
                 let a = lhs.extract_constant().to_bool();
@@ -214,7 +297,7 @@ impl Expr {
                     BinOp::Gt => a > b,
                     BinOp::Le => a <= b,
                     BinOp::Lt => a < b,
-                    _ => todo!("Impossible"),
+                    _ => unreachable!(),
                 }
             } else {
                 assert!(lhs.ty().is_primitive_ptr());
@@ -232,7 +315,7 @@ impl Expr {
                 BinOp::Gt => self.ctx.gt(lhs, rhs),
                 BinOp::Le => self.ctx.le(lhs, rhs),
                 BinOp::Lt => self.ctx.lt(lhs, rhs),
-                _ => todo!("Impossible"),
+                _ => unreachable!(),
             };
         }
     }
@@ -291,7 +374,7 @@ impl Expr {
                     *self = self.ctx.implies(lhs, rhs);
                 }
             }
-            _ => todo!("Impossible"),
+            _ => unreachable!(),
         };
     }
 
@@ -319,7 +402,8 @@ impl Expr {
                     self.id = Context::TRUE_ID;
                 }
             }
-            _ => todo!("Not support"),
+            // The meta of pointers is kept as it is
+            UnOp::Meta => {}
         }
     }
 
@@ -1,5 +1,7 @@
 use std::fmt::Debug;
 
+use num_bigint::BigInt;
+use rustc_smir::rustc_internal;
 use stable_mir::CrateDef;
 use stable_mir::abi::LayoutShape;
 use stable_mir::mir::mono::Instance;
@@ -32,11 +34,57 @@ pub type FunctionDef = (FnDef, GenericArgs);
 /// `Box::<T>::new`: Box::new uses `exchange_malloc` to alloc memory and assigns the value to
 /// the allocated memory. It just use `Global::allocate` to alloc memory. It is a wrapper of
 /// `std::alloc::alloc` and `std::alloc::alloc_zeroed`.
+///
+/// `Vec::*`: The buffer of a `Vec` is managed by `RawVec` with raw bytes and layouts, which
+/// does not fit our field-level memory model. Thus, the api of `Vec` is executed by semantic.
+///
+/// `String::*`, `str::*`: A `String` wraps a `Vec<u8>`, and a `str` is a slice of bytes. They
+/// are executed by the semantic of `Vec` and slices.
 const STD_BUILTIN_FUNCTIONS: &[&str] = &[
     "std::alloc::alloc",
     "std::alloc::dealloc",
     // Box
     "std::boxed::Box::<T>::new",
+    // Vec
+    "std::vec::Vec::<T>::new",
+    "std::vec::Vec::<T>::with_capacity",
+    "std::vec::Vec::<T, A>::push",
+    "std::vec::Vec::<T, A>::pop",
+    "std::vec::Vec::<T, A>::len",
+    "std::vec::Vec::<T, A>::capacity",
+    "std::vec::Vec::<T, A>::insert",
+    "std::vec::Vec::<T, A>::remove",
+    "std::vec::Vec::<T, A>::truncate",
+    "std::vec::Vec::<T, A>::clear",
+    "std::vec::Vec::<T, A>::as_slice",
+    "std::vec::Vec::<T, A>::as_mut_slice",
+    "std::vec::Vec::<T, A>::reserve",
+    "std::vec::Vec::<T, A>::extend_from_slice",
+    "<std::vec::Vec<T, A> as std::ops::Index<I>>::index",
+    "<std::vec::Vec<T, A> as std::ops::IndexMut<I>>::index_mut",
+    "<std::vec::Vec<T, A> as std::ops::Deref>::deref",
+    "<std::vec::Vec<T, A> as std::ops::DerefMut>::deref_mut",
+    // String
+    "std::string::String::new",
+    "std::string::String::with_capacity",
+    "std::string::String::push_str",
+    "std::string::String::len",
+    "std::string::String::capacity",
+    "std::string::String::is_empty",
+    "std::string::String::clear",
+    "std::string::String::as_bytes",
+    "std::string::String::as_str",
+    "<std::string::String as std::ops::Deref>::deref",
+    "<std::string::String as std::ops::Index<I>>::index",
+    "<std::string::String as std::convert::From<&str>>::from",
+    "<std::string::String as std::cmp::PartialEq>::eq",
+    "<std::string::String as std::cmp::PartialEq<str>>::eq",
+    "<std::string::String as std::cmp::PartialEq<&'a str>>::eq",
+    "<std::string::String as std::cmp::PartialEq>::ne",
+    "<std::string::String as std::cmp::PartialEq<str>>::ne",
+    "<std::string::String as std::cmp::PartialEq<&'a str>>::ne",
+    "<str as std::string::SpecToString>::spec_to_string",
+    "std::str::<impl std::borrow::ToOwned for str>::to_owned",
     // Layout
     "std::alloc::Layout::new",
     "std::alloc::Layout::for_value_raw",
@@ -50,13 +98,47 @@ const STD_BUILTIN_FUNCTIONS: &[&str] = &[
     "std::ptr::mut_ptr::<impl *mut T>::offset",
     "std::ptr::mut_ptr::<impl *mut T>::is_null",
     // Slice
+    "core::slice::<impl [T]>::get",
+    "core::slice::<impl [T]>::get_mut",
     "core::slice::index::slice_index_order_fail",
     "core::slice::index::slice_start_index_len_fail",
     "core::slice::index::slice_end_index_len_fail",
+    // Str
+    "core::str::<impl str>::len",
+    "core::str::<impl str>::is_empty",
+    "core::str::<impl str>::as_bytes",
+    "core::str::traits::<impl std::ops::Index<I> for str>::index",
+    "core::str::traits::<impl std::cmp::PartialEq for str>::eq",
+    "core::str::traits::<impl std::cmp::PartialEq for str>::ne",
     // Panic
     "core::panicking:panic_nounwind",
 ];
 
+/// Builtin functions that allocate, free or write memory. The elements of a `Vec` are
+/// written by the returned pointers of `index_mut`, `deref_mut`, etc.
+const STD_MEMORY_FUNCTIONS: &[&str] = &[
+    "std::alloc::alloc",
+    "std::alloc::dealloc",
+    "std::boxed::Box::<T>::new",
+    "std::vec::Vec::<T>::new",
+    "std::vec::Vec::<T>::with_capacity",
+    "std::vec::Vec::<T, A>::push",
+    "std::vec::Vec::<T, A>::insert",
+    "std::vec::Vec::<T, A>::remove",
+    "std::vec::Vec::<T, A>::as_mut_slice",
+    "std::vec::Vec::<T, A>::reserve",
+    "std::vec::Vec::<T, A>::extend_from_slice",
+    "<std::vec::Vec<T, A> as std::ops::IndexMut<I>>::index_mut",
+    "<std::vec::Vec<T, A> as std::ops::DerefMut>::deref_mut",
+    "core::slice::<impl [T]>::get_mut",
+    "std::string::String::new",
+    "std::string::String::with_capacity",
+    "std::string::String::push_str",
+    "<std::string::String as std::convert::From<&str>>::from",
+    "<str as std::string::SpecToString>::spec_to_string",
+    "std::str::<impl std::borrow::ToOwned for str>::to_owned",
+];
+
 /// To leverage the place state, some functions' semantic must be execed after unwinding.
 /// For example, `Box::<T>::from_raw` may take the ownership of the object it points to.
 const STD_FUNCTION_WITH_SPECIAL_SEMANTIC: &[&str] =
@@ -107,6 +189,10 @@ impl Type {
         Type::from(Ty::from_rigid_kind(RigidTy::Slice(elem_ty.0)))
     }
 
+    pub fn str_type() -> Self {
+        Type::from(Ty::from_rigid_kind(RigidTy::Str))
+    }
+
     pub fn slice_type_from_array_type(array_type: Type) -> Self {
         assert!(array_type.is_array());
         Type::slice_type(array_type.elem_type())
@@ -153,6 +239,10 @@ impl Type {
         self.0.kind().is_integral()
     }
 
+    pub fn is_float(&self) -> bool {
+        self.0.kind().is_float()
+    }
+
     pub fn is_primitive(&self) -> bool {
         self.0.kind().is_primitive()
     }
@@ -165,12 +255,25 @@ impl Type {
         self.is_enum() && self.name() == "Option"
     }
 
+    pub fn is_vec(&self) -> bool {
+        self.is_struct() && self.name() == "Vec"
+    }
+
+    pub fn is_string(&self) -> bool {
+        self.is_struct() && self.name() == "String"
+    }
+
     pub fn is_array(&self) -> bool {
         self.0.kind().is_array()
     }
 
+    /// `str` is a slice of bytes in our memory model
     pub fn is_slice(&self) -> bool {
-        self.0.kind().is_slice()
+        self.0.kind().is_slice() || self.is_str()
+    }
+
+    pub fn is_str(&self) -> bool {
+        self.0.kind().is_str()
     }
 
     pub fn is_fn(&self) -> bool {
@@ -215,6 +318,16 @@ impl Type {
         }
     }
 
+    /// `*mut T` or `&mut T`
+    pub fn is_mut_ptr(&self) -> bool {
+        match self.0.kind() {
+            TyKind::RigidTy(RigidTy::RawPtr(_, m)) | TyKind::RigidTy(RigidTy::Ref(_, _, m)) => {
+                m == Mutability::Mut
+            }
+            _ => false,
+        }
+    }
+
     pub fn is_slice_ptr(&self) -> bool {
         self.is_primitive_ptr() && self.pointee_ty().is_slice()
     }
@@ -242,6 +355,50 @@ impl Type {
         return self.fn_def().0.name() == "rbmc::nondet";
     }
 
+    pub fn is_rbmc_arbitrary(&self) -> bool {
+        self.is_fn() && self.fn_def().0.name() == "rbmc::Arbitrary::any"
+    }
+
+    pub fn is_rbmc_nondet_array(&self) -> bool {
+        self.is_fn() && self.fn_def().0.name() == "rbmc::nondet_array"
+    }
+
+    pub fn is_rbmc_nondet_range(&self) -> bool {
+        self.is_fn() && self.fn_def().0.name() == "rbmc::nondet_range"
+    }
+
+    pub fn is_rbmc_nondet_slice(&self) -> bool {
+        self.is_fn() && self.fn_def().0.name() == "rbmc::nondet_slice"
+    }
+
+    pub fn is_rbmc_nondet_vec(&self) -> bool {
+        self.is_fn() && self.fn_def().0.name() == "rbmc::nondet_vec"
+    }
+
+    pub fn is_rbmc_assume(&self) -> bool {
+        self.is_fn() && self.fn_def().0.name() == "rbmc::assume"
+    }
+
+    pub fn is_rbmc_assert(&self) -> bool {
+        self.is_fn() && self.fn_def().0.name() == "rbmc::assert"
+    }
+
+    pub fn is_rbmc_cover(&self) -> bool {
+        self.is_fn() && self.fn_def().0.name() == "rbmc::cover"
+    }
+
+    pub fn is_rbmc_loop_invariant(&self) -> bool {
+        self.is_fn() && self.fn_def().0.name() == "rbmc::loop_invariant"
+    }
+
+    pub fn is_rbmc_requires(&self) -> bool {
+        self.is_fn() && self.fn_def().0.name() == "rbmc::contract::requires"
+    }
+
+    pub fn is_rbmc_ensures(&self) -> bool {
+        self.is_fn() && self.fn_def().0.name() == "rbmc::contract::ensures"
+    }
+
     pub fn is_rust_builtin_function(&self) -> bool {
         if !self.is_fn() {
             return false;
@@ -250,8 +407,28 @@ impl Type {
         return STD_BUILTIN_FUNCTIONS.contains(&name.as_str());
     }
 
+    pub fn is_memory_function(&self) -> bool {
+        if !self.is_fn() {
+            return false;
+        }
+        let name = self.fn_def().0.name();
+        STD_MEMORY_FUNCTIONS.contains(&name.as_str())
+    }
+
     pub fn is_builtin_function(&self) -> bool {
-        self.is_rbmc_nondet() || self.is_rust_builtin_function()
+        self.is_rbmc_nondet()
+            || self.is_rbmc_arbitrary()
+            || self.is_rbmc_nondet_array()
+            || self.is_rbmc_nondet_range()
+            || self.is_rbmc_nondet_slice()
+            || self.is_rbmc_nondet_vec()
+            || self.is_rbmc_assume()
+            || self.is_rbmc_assert()
+            || self.is_rbmc_cover()
+            || self.is_rbmc_loop_invariant()
+            || self.is_rbmc_requires()
+            || self.is_rbmc_ensures()
+            || self.is_rust_builtin_function()
     }
 
     pub fn is_function_with_special_semantic(&self) -> bool {
@@ -267,7 +444,7 @@ impl Type {
     }
 
     pub fn contains_ptr_field(&self) -> bool {
-        if self.is_bool() || self.is_integer() || self.is_unit() {
+        if self.is_bool() || self.is_integer() || self.is_float() || self.is_unit() {
             return false;
         }
 
@@ -324,6 +501,18 @@ impl Type {
         }
     }
 
+    /// The element type of a `Vec`
+    pub fn vec_elem_type(&self) -> Self {
+        assert!(self.is_vec());
+        match self.0.kind().rigid() {
+            Some(RigidTy::Adt(_, args)) => match &args.0[0] {
+                GenericArgKind::Type(ty) => Type::from(*ty),
+                _ => panic!(),
+            },
+            _ => panic!(),
+        }
+    }
+
     pub fn array_len(&self) -> Option<usize> {
         assert!(self.is_array());
         let size = match self.0.kind() {
@@ -349,12 +538,30 @@ impl Type {
         if let TyKind::RigidTy(r) = self.0.kind() {
             return match r {
                 RigidTy::Array(t, _) | RigidTy::Slice(t) => Type::from(t),
+                RigidTy::Str => Type::unsigned_type(UintTy::U8),
                 _ => panic!("Impossible"),
             };
         }
         panic!("Impossible")
     }
 
+    /// The type of discriminants of an enum
+    pub fn enum_discriminant_type(&self) -> Type {
+        assert!(self.is_enum());
+        Type(self.0.kind().discriminant_ty().unwrap())
+    }
+
+    /// The discriminant value of each variant of an enum
+    pub fn enum_discriminants(&self) -> Vec<BigInt> {
+        assert!(self.is_enum());
+        let discr_ty = self.enum_discriminant_type();
+        let TyKind::RigidTy(RigidTy::Adt(adt, _)) = self.0.kind() else { unreachable!() };
+        rustc_middle::ty::tls::with(|tcx| {
+            let adt = rustc_internal::internal(tcx, adt);
+            adt.discriminants(tcx).map(|(_, d)| discr_ty.wrap_integer(d.val.into())).collect()
+        })
+    }
+
     pub fn enum_def(&self) -> EnumDef {
         assert!(self.is_enum());
         let mut def = (self.name(), Vec::new());
@@ -461,9 +668,11 @@ impl Type {
             RigidTy::Char => "char".into(),
             RigidTy::Int(i) => format!("{i:?}").to_lowercase().into(),
             RigidTy::Uint(i) => format!("{i:?}").to_lowercase().into(),
+            RigidTy::Float(f) => format!("{f:?}").to_lowercase().into(),
             RigidTy::Adt(def, _) => def.trimmed_name().into(),
             RigidTy::Array(ty, ..) => format!("Array({:?})", Type(*ty).name()).into(),
             RigidTy::Slice(ty) => format!("Slice({:?})", Type(*ty).name()).into(),
+            RigidTy::Str => "str".into(),
             RigidTy::RawPtr(ty, ..) => format!("Ptr({:?})", Type(*ty).name()).into(),
             RigidTy::Ref(_, ty, _) => format!("Ref({:?})", Type(*ty).name()).into(),
             RigidTy::Never => "never".into(),
@@ -487,6 +696,25 @@ impl Type {
         self.0.layout().expect("No layout?").shape().size.bytes()
     }
 
+    /// The minimal value of an integer type
+    pub fn min_value(&self) -> BigInt {
+        assert!(self.is_integer());
+        if self.is_signed() { -(BigInt::from(1) << (self.size() * 8 - 1)) } else { BigInt::ZERO }
+    }
+
+    /// The maximal value of an integer type
+    pub fn max_value(&self) -> BigInt {
+        assert!(self.is_integer());
+        let bits = if self.is_signed() { self.size() * 8 - 1 } else { self.size() * 8 };
+        (BigInt::from(1) << bits) - 1
+    }
+
+    /// Wrap `value` around the bounds of an integer type
+    pub fn wrap_integer(&self, value: BigInt) -> BigInt {
+        let m = self.max_value() - self.min_value() + 1;
+        ((value - self.min_value()) % &m + &m) % &m + self.min_value()
+    }
+
     pub fn align(&self) -> usize {
         (self.0.layout().expect("Not layout").shape().abi_align / 8) as usize
     }
@@ -6,12 +6,15 @@ extern crate rustc_interface;
 extern crate rustc_middle;
 #[macro_use]
 extern crate rustc_smir;
+extern crate rustc_span;
 extern crate stable_mir;
 
+use rust_bmc::{RBMC_CRATE, exit_code};
 use rustc_smir::{run, rustc_internal};
 use stable_mir::CompilerError;
 
 use std::ops::ControlFlow;
+use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::process::ExitCode;
 
 mod bmc;
@@ -24,27 +27,43 @@ mod symex;
 mod vc;
 
 use crate::bmc::bmc::Bmc;
-use crate::config::cli::{self, Cli};
+use crate::bmc::verdict::Verdict;
+use crate::config::cli::Cli;
 use crate::config::config::Config;
 
 fn main() -> ExitCode {
     let cli = Cli::new();
-    match run!(cli.rustc_args(), || start_bmc(cli)) {
-        Ok(_) | Err(CompilerError::Skipped) | Err(CompilerError::Interrupted(_)) => {
-            ExitCode::SUCCESS
+    symex::unsupported::install_panic_hook();
+    // Internal errors of RBMC are reported by panics
+    let res = catch_unwind(AssertUnwindSafe(|| run!(cli.rustc_args(), || start_bmc(cli))));
+    match res {
+        // `cargo rbmc` collects verdicts from the output. Compiling should
+        // not fail because of a verdict.
+        Ok(Ok(Some(verdict))) if std::env::var(RBMC_CRATE).is_err() => {
+            ExitCode::from(verdict.exit_code())
         }
-        _ => ExitCode::FAILURE,
+        Ok(Ok(_)) | Ok(Err(CompilerError::Skipped)) => ExitCode::SUCCESS,
+        // Errors in the options are reported by `start_bmc`, which interrupts
+        // the compilation
+        _ => ExitCode::from(exit_code::ERROR as u8),
     }
 }
 
-fn start_bmc(cli: Cli) -> ControlFlow<()> {
+fn start_bmc(cli: Cli) -> ControlFlow<(), Option<Verdict>> {
     // Verify when the current crate is variable.
     let local_crate = stable_mir::local_crate().name;
     if !cli.cur_crate().is_empty()
-        || matches!(std::env::var(cli::RBMC_CRATE), Ok(x) if local_crate == x)
+        || matches!(std::env::var(RBMC_CRATE), Ok(x) if local_crate == x)
     {
-        Bmc::new(&Config::new(cli)).do_bmc();
+        let config = match Config::new(cli) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("error: {e}");
+                return ControlFlow::Break(());
+            }
+        };
+        return ControlFlow::Continue(Some(Bmc::run(&config, config.entry_functions())));
     }
 
-    ControlFlow::Continue(())
+    ControlFlow::Continue(None)
 }
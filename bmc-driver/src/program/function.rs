@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::ops::RangeInclusive;
 
+use stable_mir::mir::alloc::GlobalAlloc;
 use stable_mir::mir::mono::Instance;
+use stable_mir::mir::mono::StaticDef;
 use stable_mir::mir::*;
 use stable_mir::ty::FnDef;
 use stable_mir::*;
@@ -14,6 +17,28 @@ pub type FunctionIdx = usize;
 pub type Args = Vec<Local>;
 pub type Pc = BasicBlockIdx;
 
+/// Effects of the blocks of a loop, or of a function called in a loop
+#[derive(Default)]
+pub struct Writes {
+    pub places: Vec<Place>,
+    pub statics: Vec<StaticDef>,
+    /// The arguments of a function whose pointees are written
+    pub args: Vec<usize>,
+}
+
+/// Effects of the function called in a loop
+pub type CalleeWrites<'a> = dyn FnMut(NString) -> Result<Writes, String> + 'a;
+
+fn unique<T: PartialEq>(items: Vec<T>) -> Vec<T> {
+    let mut unique = Vec::new();
+    for item in items {
+        if !unique.contains(&item) {
+            unique.push(item);
+        }
+    }
+    unique
+}
+
 /// A wrapper for functiom item in MIR
 pub struct Function {
     name: NString,
@@ -22,8 +47,10 @@ pub struct Function {
     ty: Type,
     /// Record the locals without StorageLive
     _local_alive: HashSet<Local>,
-    /// Record loop entries
-    _loop_entries: HashSet<Pc>,
+    /// Record loop entries and the source of their back-edges
+    _loop_entries: HashMap<Pc, Pc>,
+    /// Record the blocks calling `rbmc::loop_invariant` in loops
+    _loop_invariants: HashMap<Pc, Pc>,
 }
 
 impl Function {
@@ -56,12 +83,31 @@ impl Function {
                 TerminatorKind::Goto { target } => {
                     if *target < i {
                         // Back edge
-                        self._loop_entries.insert(*target);
+                        let src = self._loop_entries.entry(*target).or_insert(i);
+                        *src = (*src).max(i);
                     }
                 }
                 _ => {}
             }
         }
+        // An invariant belongs to the innermost loop
+        for i in 0..self.body.blocks.len() {
+            let TerminatorKind::Call { func, .. } = &self.basicblock(i).terminator.kind else {
+                continue;
+            };
+            if !self.operand_type(func).is_rbmc_loop_invariant() {
+                continue;
+            }
+            let entry = self
+                ._loop_entries
+                .iter()
+                .filter(|&(&entry, &src)| entry <= i && i <= src)
+                .min_by_key(|&(&entry, &src)| src - entry)
+                .map(|(&entry, _)| entry);
+            if let Some(entry) = entry {
+                self._loop_invariants.entry(entry).or_insert(i);
+            }
+        }
     }
 
     fn reconstruct_body(&mut self) {
@@ -233,6 +279,18 @@ impl Function {
         &self._local_alive
     }
 
+    /// The name of a user variable. Temporaries have no name.
+    pub fn local_name(&self, local: Local) -> Option<NString> {
+        self.body.var_debug_info.iter().find_map(|info| match &info.value {
+            VarDebugInfoContents::Place(place)
+                if place.local == local && place.projection.is_empty() =>
+            {
+                Some(NString::from(info.name.clone()))
+            }
+            _ => None,
+        })
+    }
+
     pub fn local_decl(&self, local: Local) -> &LocalDecl {
         assert!(local < self.locals().len());
         self.body.local_decl(local).unwrap()
@@ -256,7 +314,187 @@ impl Function {
     }
 
     pub fn is_loop_entry(&self, pc: Pc) -> bool {
-        self._loop_entries.contains(&pc)
+        self._loop_entries.contains_key(&pc)
+    }
+
+    /// The block calling `rbmc::loop_invariant` in the loop at `entry`
+    pub fn loop_invariant(&self, entry: Pc) -> Option<Pc> {
+        self._loop_invariants.get(&entry).copied()
+    }
+
+    /// Whether the loop at `entry` can be left before reaching the block `pc`.
+    /// Blocks ending with `unreachable` are not exits.
+    pub fn loop_exits_before(&self, entry: Pc, pc: Pc) -> bool {
+        let blocks = self.loop_blocks(entry);
+        let mut visited = HashSet::new();
+        let mut stack = vec![entry];
+        while let Some(i) = stack.pop() {
+            if i == pc || !visited.insert(i) {
+                continue;
+            }
+            for j in self.basicblock(i).terminator.successors() {
+                if matches!(self.basicblock(j).terminator.kind, TerminatorKind::Unreachable) {
+                    continue;
+                }
+                if !blocks.contains(&j) {
+                    return true;
+                }
+                stack.push(j);
+            }
+        }
+        false
+    }
+
+    /// The blocks of a loop are placed between its entry and its back-edge.
+    pub fn loop_blocks(&self, entry: Pc) -> RangeInclusive<Pc> {
+        entry..=self._loop_entries[&entry]
+    }
+
+    /// The places written in the loop at `entry`. See `writes`.
+    pub fn loop_writes(&self, entry: Pc, callee: &mut CalleeWrites) -> Result<Writes, String> {
+        self.writes(self.loop_blocks(entry), false, callee)
+    }
+
+    /// The statics and the pointees of the arguments written by the function.
+    /// Its locals are not visible to the caller.
+    pub fn function_writes(&self, callee: &mut CalleeWrites) -> Result<Writes, String> {
+        self.writes(0..=self.size() - 1, true, callee)
+    }
+
+    /// The places written in `blocks`: the locals that are assigned or
+    /// mutably borrowed, the pointees of pointers defined out of the blocks
+    /// and the statics. Pointers defined in the blocks must come from
+    /// borrows or calls, whose pointees are in the result already. Writes
+    /// through pointers loaded from memory are not supported.
+    fn writes(
+        &self,
+        blocks: RangeInclusive<Pc>,
+        in_callee: bool,
+        callee: &mut CalleeWrites,
+    ) -> Result<Writes, String> {
+        let mut places = Vec::new();
+        let mut writes = Writes::default();
+        // Whether all definitions of a local in the blocks are borrows or calls
+        let mut defined = HashMap::<Local, bool>::new();
+        let mut define = |place: &Place, is_pointer: bool| {
+            if !place.projection.contains(&ProjectionElem::Deref) {
+                let is_pointer = is_pointer && place.projection.is_empty();
+                *defined.entry(place.local).or_insert(true) &= is_pointer;
+            }
+        };
+        // Pointers to statics
+        let mut static_ptrs = HashMap::<Local, StaticDef>::new();
+        for pc in blocks {
+            let bb = self.basicblock(pc);
+            for statement in bb.statements.iter() {
+                match &statement.kind {
+                    StatementKind::Assign(place, rvalue) => {
+                        let is_pointer = match rvalue {
+                            Rvalue::Ref(_, BorrowKind::Mut { .. }, borrowed)
+                            | Rvalue::AddressOf(RawPtrKind::Mut, borrowed) => {
+                                places.push(borrowed.clone());
+                                true
+                            }
+                            Rvalue::Ref(..) | Rvalue::AddressOf(..) => true,
+                            Rvalue::Use(Operand::Constant(constant)) => {
+                                match Function::static_ptr(constant) {
+                                    Some(def) if place.projection.is_empty() => {
+                                        static_ptrs.insert(place.local, def);
+                                        true
+                                    }
+                                    _ => false,
+                                }
+                            }
+                            _ => false,
+                        };
+                        define(place, is_pointer);
+                        places.push(place.clone());
+                    }
+                    StatementKind::SetDiscriminant { place, .. } | StatementKind::Deinit(place) => {
+                        define(place, false);
+                        places.push(place.clone());
+                    }
+                    _ => {}
+                }
+            }
+            match &bb.terminator.kind {
+                TerminatorKind::Call { func, args, destination, .. } => {
+                    let ty = Type::from(self.operand_type(func).function_instance().ty());
+                    if ty.is_memory_function() {
+                        return Err(format!("call to {}", ty.fn_def().0.name()));
+                    }
+                    // Other builtin functions do not write memory
+                    if !ty.is_builtin_function() {
+                        let effects = callee(ty.function_instance().trimmed_name().into())?;
+                        writes.statics.extend(effects.statics);
+                        for i in effects.args {
+                            match &args[i] {
+                                Operand::Copy(place) | Operand::Move(place) => {
+                                    let mut pointee = place.clone();
+                                    pointee.projection.push(ProjectionElem::Deref);
+                                    places.push(pointee);
+                                }
+                                Operand::Constant(constant) => match Function::static_ptr(constant)
+                                {
+                                    Some(def) => writes.statics.push(def),
+                                    None => return Err(format!("write through {constant:?}")),
+                                },
+                            }
+                        }
+                    }
+                    define(destination, true);
+                    places.push(destination.clone());
+                }
+                TerminatorKind::Drop { .. } => return Err("drop".to_string()),
+                _ => {}
+            }
+        }
+
+        for place in places {
+            let derefs = place.projection.iter().filter(|&p| *p == ProjectionElem::Deref).count();
+            let Some(i) = place.projection.iter().position(|p| *p == ProjectionElem::Deref) else {
+                if !in_callee {
+                    writes.places.push(Place::from(place.local));
+                }
+                continue;
+            };
+            if derefs > 1 {
+                return Err(format!("write through {place:?}"));
+            }
+            if let Some(&def) = static_ptrs.get(&place.local) {
+                if i == 0 {
+                    writes.statics.push(def);
+                    continue;
+                }
+            }
+            match defined.get(&place.local) {
+                Some(true) => {}
+                None if !in_callee => {
+                    let projection = place.projection[..=i].to_vec();
+                    writes.places.push(Place { local: place.local, projection });
+                }
+                None if i == 0 && self.args.contains(&place.local) => {
+                    writes.args.push(place.local - 1);
+                }
+                _ => return Err(format!("write through {place:?}")),
+            }
+        }
+        writes.places = unique(writes.places);
+        writes.statics = unique(writes.statics);
+        writes.args = unique(writes.args);
+        Ok(writes)
+    }
+
+    /// The static pointed to by a constant pointer
+    fn static_ptr(constant: &ConstOperand) -> Option<StaticDef> {
+        let ty::ConstantKind::Allocated(allocation) = constant.const_.kind() else { return None };
+        match allocation.provenance.ptrs.as_slice() {
+            [(_, prov)] => match GlobalAlloc::from(prov.0) {
+                GlobalAlloc::Static(def) => Some(def),
+                _ => None,
+            },
+            _ => None,
+        }
     }
 
     pub fn operand_type(&self, operand: &Operand) -> Type {
@@ -288,7 +526,8 @@ impl From<(NString, Body, Type)> for Function {
             body: value.1,
             ty: value.2,
             _local_alive: HashSet::new(),
-            _loop_entries: HashSet::new(),
+            _loop_entries: HashMap::new(),
+            _loop_invariants: HashMap::new(),
         };
         function.init();
         function
@@ -2,12 +2,17 @@ use std::collections::HashMap;
 
 use num_bigint::BigInt;
 use num_bigint::Sign;
-use stable_mir::mir::TerminatorKind;
+use rustc_smir::rustc_internal;
+use stable_mir::crate_def::Attribute;
+use stable_mir::mir::{LocalDecl, Operand, TerminatorKind};
+use stable_mir::mir::mono::Instance;
 use stable_mir::mir::mono::StaticDef;
 use stable_mir::target::*;
+use stable_mir::ty::MirConst;
 use stable_mir::*;
 
 use super::function::*;
+use crate::config::cli::parse_stub;
 use crate::expr::ty::Type;
 use crate::symbol::nstring::NString;
 
@@ -18,29 +23,73 @@ pub struct Program {
     local_function_count: usize,
     functions: Vec<Function>,
     function_map: HashMap<NString, FunctionIdx>,
+    /// Functions marked by `#[rbmc::proof]`
+    harnesses: Vec<NString>,
+    /// Loop bounds from `#[rbmc::unwind(N)]`
+    unwind_attrs: HashMap<NString, usize>,
+    /// Functions with contracts
+    contracts: Vec<NString>,
+    /// Stubs from `#[rbmc::stub(..)]` and `--stub`
+    stubs: HashMap<NString, NString>,
 }
 
 impl Program {
-    pub fn new(_crate: Crate, entry_function: NString) -> Self {
+    /// Errors in the stubs and the attributes are returned as messages for
+    /// users
+    pub fn new(_crate: Crate, stub_specs: &[String]) -> Result<Self, String> {
         let mut functions = Vec::new();
-        _crate.fn_defs().iter().for_each(|def| functions.push(Function::from(def)));
+        let mut harnesses = Vec::new();
+        let mut unwind_attrs = HashMap::new();
+        let mut contracts = Vec::new();
+        let mut stubs = HashMap::new();
+        // Foreign functions have no body
+        for def in _crate.fn_defs().iter().filter(|def| def.has_body()) {
+            functions.push(Function::from(def));
+            if !def.tool_attrs(&["rbmctool".into(), "proof".into()]).is_empty() {
+                harnesses.push(NString::from(def.trimmed_name()));
+            }
+            if let Some(attr) = def.tool_attrs(&["rbmctool".into(), "unwind".into()]).first() {
+                unwind_attrs.insert(NString::from(def.trimmed_name()), unwind_bound(attr)?);
+            }
+            if !def.tool_attrs(&["rbmctool".into(), "contract".into()]).is_empty() {
+                contracts.push(NString::from(def.trimmed_name()));
+            }
+            if let Some(attr) = def.tool_attrs(&["rbmctool".into(), "stub".into()]).first() {
+                stubs.insert(stub_original(attr)?, NString::from(def.trimmed_name()));
+            }
+        }
         let mut idx = HashMap::new();
         functions.iter().enumerate().for_each(|(i, function)| {
             idx.insert(function.name(), i);
         });
-        assert!(idx.contains_key(&entry_function));
+        // Stubs from the command line override the attributes
+        for spec in stub_specs {
+            let (original, replacement) = parse_stub(spec)?;
+            stubs.insert(original, replacement);
+        }
+        for (original, replacement) in stubs.iter() {
+            if !idx.contains_key(replacement) {
+                return Err(format!(
+                    "The stub {replacement:?} of {original:?} is not a function in the local crate"
+                ));
+            }
+        }
         let mut program = Program {
             name: _crate.name.clone().into(),
             static_variables: _crate.statics(),
             local_function_count: functions.len(),
             functions: functions,
             function_map: idx,
+            harnesses,
+            unwind_attrs,
+            contracts,
+            stubs,
         };
-        program.init();
-        program
+        program.init()?;
+        Ok(program)
     }
 
-    fn init(&mut self) {
+    fn init(&mut self) -> Result<(), String> {
         // Cache all reachable funtions
         let mut i = 0;
         while i < self.functions.len() {
@@ -51,12 +100,22 @@ impl Program {
                 let instance = match &bb.terminator.kind {
                     TerminatorKind::Drop { place, .. } => {
                         let ty = Type::from(place.ty(locals).unwrap());
-                        Some(ty.drop_instance())
+                        // `Vec` is dropped by its semantic in symex
+                        if ty.is_vec() { None } else { Some(ty.drop_instance()) }
                     }
-                    TerminatorKind::Call { func, .. } => {
-                        let ty = Type::from(func.ty(locals).unwrap());
-                        let instance = ty.function_instance();
-                        if ty.is_builtin_function() || !instance.has_body() {
+                    TerminatorKind::Call { func, args, destination, .. } => {
+                        let instance = Type::from(func.ty(locals).unwrap()).function_instance();
+                        // Methods of traits are resolved to their implementations
+                        let ty = Type::from(instance.ty());
+                        let ret_ty = Type::from(destination.ty(locals).unwrap());
+                        if let Some(stub) = self.stub(&instance) {
+                            self.check_stub(stub, &instance, args, ret_ty, locals)?;
+                        }
+                        // Stubs are in the local crate
+                        if self.stub(&instance).is_some()
+                            || ty.is_builtin_function()
+                            || !instance.has_body()
+                        {
                             None
                         } else {
                             Some(instance)
@@ -77,12 +136,63 @@ impl Program {
             new_functions.into_iter().for_each(|function| self.functions.push(function));
             i += 1;
         }
+        Ok(())
     }
 
     pub fn static_variables(&self) -> &Vec<StaticDef> {
         &self.static_variables
     }
 
+    pub fn harnesses(&self) -> &Vec<NString> {
+        &self.harnesses
+    }
+
+    pub fn unwind_attr(&self, function: NString) -> Option<usize> {
+        self.unwind_attrs.get(&function).copied()
+    }
+
+    pub fn contracts(&self) -> &Vec<NString> {
+        &self.contracts
+    }
+
+    pub fn has_contract(&self, function: NString) -> bool {
+        self.contracts.contains(&function)
+    }
+
+    /// Stubs must have the signatures of the functions they replace. The
+    /// signature of a call is given by its arguments and its destination.
+    fn check_stub(
+        &self,
+        stub: NString,
+        instance: &Instance,
+        args: &[Operand],
+        ret_ty: Type,
+        locals: &[LocalDecl],
+    ) -> Result<(), String> {
+        let stub_function = &self.functions[self.function_map[&stub]];
+        let is_matched = stub_function.args().len() == args.len()
+            && stub_function.local_type(0) == ret_ty
+            && args.iter().enumerate().all(|(i, arg)| {
+                // The arguments are the locals following the return place
+                stub_function.local_type(i + 1) == Type::from(arg.ty(locals).unwrap())
+            });
+        if !is_matched {
+            return Err(format!(
+                "The stub {stub:?} does not match the signature of {}",
+                instance.name()
+            ));
+        }
+        Ok(())
+    }
+
+    /// The stub replacing the function of `instance`. It is given by the path
+    /// or the trimmed name of the function.
+    pub fn stub(&self, instance: &Instance) -> Option<NString> {
+        let name = NString::from(instance.name());
+        let trimmed_name = NString::from(instance.trimmed_name());
+        self.stubs.get(&name).or_else(|| self.stubs.get(&trimmed_name)).copied()
+    }
+
     pub fn contains_function(&self, name: NString) -> bool {
         self.function_map.contains_key(&name)
     }
@@ -96,6 +206,29 @@ impl Program {
         *self.function_map.get(&name).unwrap()
     }
 
+    /// The places and statics written in the loop at `entry` of `function`,
+    /// including the effects of the functions called in the loop. Functions
+    /// without bodies are not supported except `rbmc::nondet`.
+    pub fn loop_writes(&self, function: &Function, entry: Pc) -> Result<Writes, String> {
+        let mut calls = Vec::new();
+        function.loop_writes(entry, &mut |callee| self.callee_writes(callee, &mut calls))
+    }
+
+    fn callee_writes(&self, callee: NString, calls: &mut Vec<NString>) -> Result<Writes, String> {
+        let callee = self.stubs.get(&callee).copied().unwrap_or(callee);
+        if calls.contains(&callee) {
+            return Err(format!("recursive call to {callee:?}"));
+        }
+        if !self.contains_function(callee) {
+            return Err(format!("call to {callee:?}"));
+        }
+        calls.push(callee);
+        let function = self.function(self.function_id(callee));
+        let writes = function.function_writes(&mut |f| self.callee_writes(f, calls));
+        calls.pop();
+        writes
+    }
+
     pub fn function(&self, i: FunctionIdx) -> &Function {
         assert!(i < self.functions.len());
         &self.functions[i]
@@ -123,6 +256,67 @@ impl Program {
     }
 }
 
+/// The original function in `#[rbmctool::stub(PATH)]`
+fn stub_original(attr: &Attribute) -> Result<NString, String> {
+    let text = attr.as_str();
+    match text.split_once('(').and_then(|(_, rest)| rest.rsplit_once(')')) {
+        Some((path, _)) => Ok(path.replace(' ', "").into()),
+        None => Err(format!("Invalid attribute {text}")),
+    }
+}
+
+/// The bound in `#[rbmctool::unwind(N)]`
+fn unwind_bound(attr: &Attribute) -> Result<usize, String> {
+    let text = attr.as_str();
+    let bound = text.split_once('(').and_then(|(_, rest)| rest.split_once(')'));
+    match bound {
+        Some((n, _)) => n.trim().parse().map_err(|_| format!("Invalid loop bound in {text}")),
+        None => Err(format!("Invalid attribute {text}")),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractMode {
+    /// Check the function against its contract
+    Check,
+    /// Replace calls to the function by its contract
+    Replace,
+}
+
+impl ContractMode {
+    fn prefix(&self) -> &'static str {
+        match self {
+            ContractMode::Check => "__rbmc_check_",
+            ContractMode::Replace => "__rbmc_replace_",
+        }
+    }
+}
+
+/// The function generated by the contract of `function`, e.g.,
+/// `S::__rbmc_check_f` for `S::f`
+pub fn contract_wrapper(function: NString, mode: ContractMode) -> NString {
+    let name = function.to_string();
+    let (path, f) = split_path(&name);
+    NString::from(format!("{path}{}{f}", mode.prefix()))
+}
+
+/// The function and the mode of a function generated by contracts
+pub fn contract_of(wrapper: NString) -> Option<(NString, ContractMode)> {
+    let name = wrapper.to_string();
+    let (path, f) = split_path(&name);
+    [ContractMode::Check, ContractMode::Replace].into_iter().find_map(|mode| {
+        f.strip_prefix(mode.prefix()).map(|f| (NString::from(format!("{path}{f}")), mode))
+    })
+}
+
+/// `("S::", "f")` for `S::f`
+fn split_path(name: &str) -> (&str, &str) {
+    match name.rfind("::") {
+        Some(i) => name.split_at(i + 2),
+        None => ("", name),
+    }
+}
+
 pub(crate) fn read_target_integer(bytes: &[u8]) -> BigInt {
     match MachineInfo::target().endian {
         Endian::Big => BigInt::from_signed_bytes_be(bytes),
@@ -130,6 +324,13 @@ pub(crate) fn read_target_integer(bytes: &[u8]) -> BigInt {
     }
 }
 
+pub(crate) fn read_target_uint(bytes: &[u8]) -> BigInt {
+    match MachineInfo::target().endian {
+        Endian::Big => BigInt::from_bytes_be(Sign::Plus, bytes),
+        Endian::Little => BigInt::from_bytes_le(Sign::Plus, bytes),
+    }
+}
+
 pub fn bigint_to_u64(bigint: &BigInt) -> u64 {
     if bigint == &BigInt::ZERO {
         return 0;
@@ -143,3 +344,13 @@ pub fn bigint_to_u64(bigint: &BigInt) -> u64 {
 pub fn bigint_to_usize(bigint: &BigInt) -> usize {
     bigint_to_u64(bigint) as usize
 }
+
+/// Evaluate a promoted constant, e.g., `&"str"`, which is unevaluated in stable MIR
+pub(crate) fn eval_promoted(mirconst: &MirConst) -> Option<MirConst> {
+    rustc_middle::ty::tls::with(|tcx| {
+        let c = rustc_internal::internal(tcx, mirconst);
+        let typing_env = rustc_middle::ty::TypingEnv::fully_monomorphized();
+        let value = c.eval(tcx, typing_env, rustc_span::DUMMY_SP).ok()?;
+        Some(rustc_internal::stable(rustc_middle::mir::Const::Val(value, c.ty())))
+    })
+}
@@ -1,7 +1,9 @@
+use std::cmp::Ordering;
 use std::fmt::Debug;
 
 use num_bigint::BigInt;
 
+use crate::config::cli::IntEncoding;
 use crate::expr::constant::Constant;
 use crate::expr::expr::*;
 use crate::expr::op::*;
@@ -19,6 +21,7 @@ pub(crate) trait SmtSolver<'ctx> {
     fn reset(&mut self);
     fn check(&self) -> PResult;
     fn eval_bool(&self, expr: Expr) -> bool;
+    fn eval_value(&self, expr: Expr) -> Option<NString>;
     fn show_model(&self);
 }
 
@@ -27,12 +30,27 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
     fn get_cache_ast(&self, expr: &Expr) -> Option<Ast>;
     fn cache_alloc_ast(&mut self, ast: Ast);
 
+    fn int_encoding(&self) -> IntEncoding;
+
+    /// Integers of type `ty` are encoded as bitvectors.
+    fn is_bv_integer(&self, ty: Type) -> bool {
+        self.int_encoding() == IntEncoding::Bv && ty.is_integer()
+    }
+
+    /// The width of bitvector for integer type `ty`.
+    fn bv_width(&self, ty: Type) -> u32 {
+        (ty.size() * 8) as u32
+    }
+
     fn convert_sort(&mut self, ty: Type) -> Sort {
         if ty.is_bool() {
             return self.mk_bool_sort();
         }
 
         if ty.is_integer() {
+            if self.is_bv_integer(ty) {
+                return self.mk_bv_sort(self.bv_width(ty));
+            }
             return self.mk_int_sort();
         }
 
@@ -111,16 +129,48 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
         if expr.is_binary() {
             let lhs = &args[0];
             let rhs = &args[1];
+            // Signed and unsigned bitvectors have different semantics for
+            // division and comparison.
+            let ty = expr.extract_lhs().ty();
+            let bv = self.is_bv_integer(ty);
+            let signed = ty.is_signed();
             a = Some(match expr.extract_bin_op() {
                 BinOp::Add => self.mk_add(lhs, rhs),
                 BinOp::Sub => self.mk_sub(lhs, rhs),
                 BinOp::Mul => self.mk_mul(lhs, rhs),
+                BinOp::Div if bv && signed => self.mk_bvsdiv(lhs, rhs),
+                BinOp::Div if bv => self.mk_bvudiv(lhs, rhs),
+                BinOp::Div if signed => self.convert_truncated_div(lhs, rhs),
                 BinOp::Div => self.mk_div(lhs, rhs),
+                BinOp::Rem if bv && signed => self.mk_bvsrem(lhs, rhs),
+                BinOp::Rem if bv => self.mk_bvurem(lhs, rhs),
+                BinOp::Rem => {
+                    let quotient = if signed {
+                        self.convert_truncated_div(lhs, rhs)
+                    } else {
+                        self.mk_div(lhs, rhs)
+                    };
+                    let product = self.mk_mul(&quotient, rhs);
+                    self.mk_sub(lhs, &product)
+                }
+                BinOp::BitAnd | BinOp::BitOr | BinOp::BitXor | BinOp::Shl | BinOp::Shr => {
+                    let rhs_ty = expr.extract_rhs().ty();
+                    self.convert_bitwise(expr.extract_bin_op(), lhs, rhs, ty, rhs_ty)
+                }
+                BinOp::Cmp => self.convert_three_way_cmp(lhs, rhs, ty, expr.ty()),
                 BinOp::Eq => self.mk_eq(lhs, rhs),
                 BinOp::Ne => self.mk_ne(lhs, rhs),
+                BinOp::Ge if bv && signed => self.mk_bvsge(lhs, rhs),
+                BinOp::Ge if bv => self.mk_bvuge(lhs, rhs),
                 BinOp::Ge => self.mk_ge(lhs, rhs),
+                BinOp::Gt if bv && signed => self.mk_bvsgt(lhs, rhs),
+                BinOp::Gt if bv => self.mk_bvugt(lhs, rhs),
                 BinOp::Gt => self.mk_gt(lhs, rhs),
+                BinOp::Le if bv && signed => self.mk_bvsle(lhs, rhs),
+                BinOp::Le if bv => self.mk_bvule(lhs, rhs),
                 BinOp::Le => self.mk_le(lhs, rhs),
+                BinOp::Lt if bv && signed => self.mk_bvslt(lhs, rhs),
+                BinOp::Lt if bv => self.mk_bvult(lhs, rhs),
                 BinOp::Lt => self.mk_lt(lhs, rhs),
                 BinOp::And => self.mk_and(lhs, rhs),
                 BinOp::Or => self.mk_or(lhs, rhs),
@@ -140,10 +190,20 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
         if expr.is_unary() {
             a = Some(match expr.extract_un_op() {
                 UnOp::Not => self.mk_not(&args[0]),
+                UnOp::Neg => {
+                    let zero = Constant::Integer(BigInt::ZERO);
+                    let zero = self.convert_constant(&zero, expr.ty()).unwrap();
+                    self.mk_sub(&zero, &args[0])
+                }
                 _ => panic!("Not support"),
             });
         }
 
+        if expr.is_overflow() {
+            let ty = expr.extract_lhs().ty();
+            a = Some(self.convert_overflow(expr.extract_bin_op(), &args[0], &args[1], ty));
+        }
+
         if expr.is_ite() {
             let cond = &args[0];
             let true_value = &args[1];
@@ -229,6 +289,42 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
         }
     }
 
+    /// The result of `lhs op rhs` is compared with the bounds of `ty`. For bitvectors,
+    /// operands are extended to the double width so that the result never wraps around.
+    fn convert_overflow(&mut self, op: BinOp, lhs: &Ast, rhs: &Ast, ty: Type) -> Ast {
+        let bv = self.is_bv_integer(ty);
+        let (lhs, rhs, min, max) = if bv {
+            let n = self.bv_width(ty);
+            let (lhs, rhs) = if ty.is_signed() {
+                (self.mk_bv_sign_ext(lhs, n), self.mk_bv_sign_ext(rhs, n))
+            } else {
+                (self.mk_bv_zero_ext(lhs, n), self.mk_bv_zero_ext(rhs, n))
+            };
+            let min = self.mk_smt_bv(ty.min_value(), 2 * n);
+            let max = self.mk_smt_bv(ty.max_value(), 2 * n);
+            (lhs, rhs, min, max)
+        } else {
+            (
+                lhs.clone(),
+                rhs.clone(),
+                self.mk_smt_int(ty.min_value()),
+                self.mk_smt_int(ty.max_value()),
+            )
+        };
+        let res = match op {
+            BinOp::Add => self.mk_add(&lhs, &rhs),
+            BinOp::Sub => self.mk_sub(&lhs, &rhs),
+            BinOp::Mul => self.mk_mul(&lhs, &rhs),
+            _ => panic!("Do not support overflow of {op:?}"),
+        };
+        let (underflow, overflow) = if bv && ty.is_signed() {
+            (self.mk_bvslt(&res, &min), self.mk_bvsgt(&res, &max))
+        } else {
+            (self.mk_lt(&res, &min), self.mk_gt(&res, &max))
+        };
+        self.mk_or(&underflow, &overflow)
+    }
+
     fn convert_terminal(&mut self, expr: Expr) -> Option<Ast> {
         let mut a = None;
         if expr.is_constant() {
@@ -252,7 +348,13 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
     fn convert_constant(&mut self, constant: &Constant, ty: Type) -> Option<Ast> {
         match constant {
             Constant::Bool(b) => Some(self.mk_smt_bool(*b)),
-            Constant::Integer(i) => Some(self.mk_smt_int(i.clone())),
+            Constant::Integer(i) => {
+                if self.is_bv_integer(ty) {
+                    Some(self.mk_smt_bv(i.clone(), self.bv_width(ty)))
+                } else {
+                    Some(self.mk_smt_int(i.clone()))
+                }
+            }
             Constant::Null(ty) => Some(self.convert_null(*ty)),
             Constant::Array(c, t) => {
                 let domain = self.convert_sort(ty.array_domain());
@@ -302,7 +404,8 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
     fn convert_array(&mut self, elem: &Vec<Ast>, ty: Type) -> Ast {
         let mut array = self.mk_fresh("array".into(), ty);
         for (i, val) in elem.iter().enumerate() {
-            let index = self.mk_smt_int(BigInt::from(i));
+            let index =
+                self.convert_constant(&Constant::Integer(i.into()), ty.array_domain()).unwrap();
             array = self.mk_store(&array, &index, val);
         }
         array
@@ -317,6 +420,9 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
             return self.mk_bool_symbol(name);
         }
         if ty.is_integer() {
+            if self.is_bv_integer(ty) {
+                return self.mk_bv_symbol(name, self.bv_width(ty));
+            }
             return self.mk_int_symbol(name);
         }
         if ty.is_primitive_ptr() {
@@ -333,7 +439,7 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
             return self.mk_tuple_symbol(name, &sort);
         }
         if ty.is_tuple() {
-            let sort = self.convert_enum_sort(ty);
+            let sort = self.convert_tuple_sort(ty);
             return self.mk_tuple_symbol(name, &sort);
         }
         if ty.is_enum() {
@@ -349,7 +455,7 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
         let inner_expr = object.extract_inner_expr();
         if inner_expr.is_symbol() {
             let base = self.convert_object_space(&object);
-            let offset = self.mk_smt_int(BigInt::ZERO);
+            let offset = self.mk_smt_usize(BigInt::ZERO);
             return self.convert_pointer(&base, &offset, None);
         }
 
@@ -375,7 +481,7 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
             let offset = if inner_object_ty.is_enum() {
                 // For enum, the discrminant occupy one field.
                 assert!(inner_offset.extract_constant().to_integer() == BigInt::ZERO);
-                self.mk_smt_int(BigInt::from(1))
+                self.mk_smt_usize(BigInt::from(1))
             } else {
                 self.convert_ast(inner_offset)
             };
@@ -406,7 +512,9 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
 
     fn convert_cast(&mut self, expr: Expr, target_ty: Type) -> Ast {
         if expr.ty().is_integer() && target_ty.is_integer() {
-            return self.convert_ast(expr.clone());
+            let src_ty = expr.ty();
+            let src = self.convert_ast(expr);
+            return self.convert_integer_cast(&src, src_ty, target_ty);
         }
 
         if expr.ty().is_primitive_ptr() {
@@ -428,13 +536,102 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
                 let pointer_offset = pt.ctx.pointer_offset(pt.clone());
                 let base = self.convert_ast(pointer_base);
                 let offset = self.convert_ast(pointer_offset);
-                return self.mk_add(&base, &offset);
+                let address = self.mk_add(&base, &offset);
+                return self.convert_integer_cast(&address, Type::usize_type(), target_ty);
             }
         }
 
         panic!("Do not support cast {:?} to {target_ty:?}", pt.ty())
     }
 
+    /// Integer division in SMT rounds towards negative infinity for positive divisors,
+    /// while Rust rounds towards zero.
+    fn convert_truncated_div(&mut self, lhs: &Ast, rhs: &Ast) -> Ast {
+        let zero = self.mk_smt_int(BigInt::ZERO);
+        let lhs_nonneg = self.mk_ge(lhs, &zero);
+        let rhs_nonneg = self.mk_ge(rhs, &zero);
+        let neg_lhs = self.mk_sub(&zero, lhs);
+        let neg_rhs = self.mk_sub(&zero, rhs);
+        let abs_lhs = self.mk_ite(&lhs_nonneg, lhs, &neg_lhs);
+        let abs_rhs = self.mk_ite(&rhs_nonneg, rhs, &neg_rhs);
+        let quotient = self.mk_div(&abs_lhs, &abs_rhs);
+        let neg_quotient = self.mk_sub(&zero, &quotient);
+        let same_sign = self.mk_eq(&lhs_nonneg, &rhs_nonneg);
+        self.mk_ite(&same_sign, &quotient, &neg_quotient)
+    }
+
+    /// Bitwise operators are defined on bitvectors. Mathematical integers are converted
+    /// to bitvectors of the type width and back. The shift amount is masked by the
+    /// width, as MIR does.
+    fn convert_bitwise(&mut self, op: BinOp, lhs: &Ast, rhs: &Ast, ty: Type, rhs_ty: Type) -> Ast {
+        let n = self.bv_width(ty);
+        let bv = self.is_bv_integer(ty);
+        let lhs = if bv { lhs.clone() } else { self.mk_int2bv(lhs, n) };
+        let rhs = if matches!(op, BinOp::Shl | BinOp::Shr) {
+            let amount = if bv {
+                self.convert_integer_cast(rhs, rhs_ty, ty)
+            } else {
+                let width = self.bv_width(rhs_ty);
+                let amount = self.mk_int2bv(rhs, width);
+                self.convert_bv_cast(&amount, rhs_ty, ty)
+            };
+            let mask = self.mk_smt_bv((n - 1).into(), n);
+            self.mk_bvand(&amount, &mask)
+        } else if bv {
+            rhs.clone()
+        } else {
+            self.mk_int2bv(rhs, n)
+        };
+        let res = match op {
+            BinOp::BitAnd => self.mk_bvand(&lhs, &rhs),
+            BinOp::BitOr => self.mk_bvor(&lhs, &rhs),
+            BinOp::BitXor => self.mk_bvxor(&lhs, &rhs),
+            BinOp::Shl => self.mk_bvshl(&lhs, &rhs),
+            BinOp::Shr if ty.is_signed() => self.mk_bvashr(&lhs, &rhs),
+            BinOp::Shr => self.mk_bvlshr(&lhs, &rhs),
+            _ => panic!("{op:?} is not bitwise"),
+        };
+        if bv { res } else { self.mk_bv2int(&res, ty.is_signed()) }
+    }
+
+    /// `lhs.cmp(rhs)` is one of `Less`, `Equal` and `Greater` in `core::cmp::Ordering`.
+    fn convert_three_way_cmp(&mut self, lhs: &Ast, rhs: &Ast, operand_ty: Type, ty: Type) -> Ast {
+        let lt = if self.is_bv_integer(operand_ty) && operand_ty.is_signed() {
+            self.mk_bvslt(lhs, rhs)
+        } else {
+            self.mk_lt(lhs, rhs)
+        };
+        let eq = self.mk_eq(lhs, rhs);
+        let less = self.convert_enum(0, None, ty);
+        let equal = self.convert_enum(1, None, ty);
+        let greater = self.convert_enum(2, None, ty);
+        let not_less = self.mk_ite(&eq, &equal, &greater);
+        self.mk_ite(&lt, &less, &not_less)
+    }
+
+    /// For bitvectors, a narrowing cast truncates the high bits, and a widening cast
+    /// extends the sign bit if the source type is signed. Mathematical integers are
+    /// left unchanged.
+    fn convert_integer_cast(&mut self, src: &Ast, src_ty: Type, target_ty: Type) -> Ast {
+        if !self.is_bv_integer(src_ty) || !self.is_bv_integer(target_ty) {
+            return src.clone();
+        }
+        self.convert_bv_cast(src, src_ty, target_ty)
+    }
+
+    fn convert_bv_cast(&mut self, src: &Ast, src_ty: Type, target_ty: Type) -> Ast {
+        let src_width = self.bv_width(src_ty);
+        let target_width = self.bv_width(target_ty);
+        match target_width.cmp(&src_width) {
+            Ordering::Less => self.mk_bv_extract(src, target_width - 1, 0),
+            Ordering::Greater if src_ty.is_signed() => {
+                self.mk_bv_sign_ext(src, target_width - src_width)
+            }
+            Ordering::Greater => self.mk_bv_zero_ext(src, target_width - src_width),
+            Ordering::Equal => src.clone(),
+        }
+    }
+
     fn convert_index(&mut self, object: Expr, index: Expr) -> Ast {
         if object.ty().is_array() {
             let array = self.convert_ast(object.clone());
@@ -450,6 +647,15 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
             return self.convert_index_enum(object.clone(), index.clone());
         }
 
+        // An element of a slice is an element of the array it slices
+        let inner_expr =
+            if object.is_object() { object.extract_inner_expr() } else { object.clone() };
+        if inner_expr.is_slice() {
+            let mut i = object.ctx.add(inner_expr.extract_slice_start(), index);
+            i.simplify();
+            return self.convert_index(inner_expr.extract_object(), i);
+        }
+
         panic!("Do not support load {object:?} with {:?}", object.ty())
     }
 
@@ -493,17 +699,45 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
     // sort
     fn mk_bool_sort(&self) -> Sort;
     fn mk_int_sort(&self) -> Sort;
+    fn mk_bv_sort(&self, width: u32) -> Sort;
     fn mk_array_sort(&mut self, domain: &Sort, range: &Sort) -> Sort;
     fn mk_pointer_sort(&self) -> Sort;
 
     // constant
     fn mk_smt_bool(&self, b: bool) -> Ast;
     fn mk_smt_int(&self, i: BigInt) -> Ast;
+    fn mk_smt_bv(&self, i: BigInt, width: u32) -> Ast;
     fn mk_smt_const_array(&self, domain: &Sort, val: &Ast) -> Ast;
 
+    /// Addresses and offsets in pointer logic have the same encoding as `usize`.
+    fn mk_usize_sort(&self) -> Sort {
+        if self.int_encoding() == IntEncoding::Bv {
+            self.mk_bv_sort(self.bv_width(Type::usize_type()))
+        } else {
+            self.mk_int_sort()
+        }
+    }
+
+    fn mk_smt_usize(&self, i: BigInt) -> Ast {
+        if self.int_encoding() == IntEncoding::Bv {
+            self.mk_smt_bv(i, self.bv_width(Type::usize_type()))
+        } else {
+            self.mk_smt_int(i)
+        }
+    }
+
+    fn mk_usize_symbol(&self, name: NString) -> Ast {
+        if self.int_encoding() == IntEncoding::Bv {
+            self.mk_bv_symbol(name, self.bv_width(Type::usize_type()))
+        } else {
+            self.mk_int_symbol(name)
+        }
+    }
+
     // symbol
     fn mk_bool_symbol(&self, name: NString) -> Ast;
     fn mk_int_symbol(&self, name: NString) -> Ast;
+    fn mk_bv_symbol(&self, name: NString, width: u32) -> Ast;
     fn mk_array_symbol(&self, name: NString, domain: &Sort, range: &Sort) -> Ast;
     fn mk_tuple_symbol(&self, name: NString, sort: &Sort) -> Ast;
     fn mk_enum_symbol(&self, name: NString, sort: &Sort) -> Ast;
@@ -531,4 +765,30 @@ pub(crate) trait Convert<Sort, Ast: Clone + Debug> {
     fn mk_not(&self, operand: &Ast) -> Ast;
     fn mk_implies(&self, cond: &Ast, conseq: &Ast) -> Ast;
     fn mk_ite(&self, cond: &Ast, true_value: &Ast, false_value: &Ast) -> Ast;
+
+    // bitvector. `mk_add`, `mk_sub` and `mk_mul` are shared with integers, and the
+    // unsigned comparisons are the default for bitvectors.
+    fn mk_bvsdiv(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvudiv(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvsrem(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvurem(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvand(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvor(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvxor(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvshl(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvashr(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvlshr(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvsge(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvuge(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvsgt(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvugt(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvsle(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvule(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvslt(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bvult(&self, lhs: &Ast, rhs: &Ast) -> Ast;
+    fn mk_bv_extract(&self, operand: &Ast, high: u32, low: u32) -> Ast;
+    fn mk_bv_sign_ext(&self, operand: &Ast, n: u32) -> Ast;
+    fn mk_bv_zero_ext(&self, operand: &Ast, n: u32) -> Ast;
+    fn mk_int2bv(&self, operand: &Ast, width: u32) -> Ast;
+    fn mk_bv2int(&self, operand: &Ast, signed: bool) -> Ast;
 }
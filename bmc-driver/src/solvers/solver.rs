@@ -1,4 +1,6 @@
+use crate::config::cli::IntEncoding;
 use crate::expr::expr::Expr;
+use crate::symbol::nstring::NString;
 
 use super::context::SolverCtx;
 use super::smt::smt_conv::*;
@@ -16,9 +18,9 @@ pub struct Solver<'ctx> {
 }
 
 impl<'ctx> Solver<'ctx> {
-    pub fn new(solver_ctx: &'ctx SolverCtx) -> Self {
+    pub fn new(solver_ctx: &'ctx SolverCtx, int_encoding: IntEncoding) -> Self {
         let mut smt_solver = match solver_ctx {
-            SolverCtx::Z3(ctx) => Box::new(Z3Conv::new(ctx)),
+            SolverCtx::Z3(ctx) => Box::new(Z3Conv::new(ctx, int_encoding)),
         };
         smt_solver.init();
         Solver { smt_solver }
@@ -45,6 +47,11 @@ impl<'ctx> Solver<'ctx> {
         self.smt_solver.eval_bool(expr)
     }
 
+    /// The value of `expr` in the model. Integers are shown in decimal.
+    pub fn eval_value(&self, expr: Expr) -> Option<NString> {
+        self.smt_solver.eval_value(expr)
+    }
+
     pub fn show_model(&self) {
         println!("Model:");
         self.smt_solver.show_model();
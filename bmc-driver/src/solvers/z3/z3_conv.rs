@@ -5,6 +5,7 @@ use num_bigint::BigInt;
 use z3;
 use z3::ast::Ast;
 
+use crate::config::cli::IntEncoding;
 use crate::expr::expr::*;
 use crate::expr::ty::Type;
 use crate::program::program::bigint_to_usize;
@@ -17,6 +18,7 @@ use crate::symbol::nstring::NString;
 pub struct Z3Conv<'ctx> {
     pub(super) z3_ctx: &'ctx z3::Context,
     z3_solver: z3::Solver<'ctx>,
+    int_encoding: IntEncoding,
     pub(super) fresh_count: HashMap<NString, usize>,
     pub(super) datatypes: HashMap<DataTypeSign, z3::DatatypeSort<'ctx>>,
     pub(super) pointer_logic: PointerLogic<z3::ast::Dynamic<'ctx>>,
@@ -27,11 +29,12 @@ pub struct Z3Conv<'ctx> {
 }
 
 impl<'ctx> Z3Conv<'ctx> {
-    pub fn new(z3_ctx: &'ctx z3::Context) -> Self {
+    pub fn new(z3_ctx: &'ctx z3::Context, int_encoding: IntEncoding) -> Self {
         let z3_solver = z3::Solver::new(z3_ctx);
         Z3Conv {
             z3_ctx,
             z3_solver,
+            int_encoding,
             fresh_count: HashMap::new(),
             datatypes: HashMap::new(),
             pointer_logic: PointerLogic::new(),
@@ -48,6 +51,26 @@ impl<'ctx> Z3Conv<'ctx> {
     pub(super) fn assert(&self, e: z3::ast::Dynamic<'ctx>) {
         self.z3_solver.assert(&e.as_bool().unwrap());
     }
+
+    /// Parse numerals of Z3, e.g., `5`, `(- 5)`, `#x05` and `#b101`.
+    fn parse_numeral(numeral: &str) -> Option<BigInt> {
+        if let Some(hex) = numeral.strip_prefix("#x") {
+            BigInt::parse_bytes(hex.as_bytes(), 16)
+        } else if let Some(bin) = numeral.strip_prefix("#b") {
+            BigInt::parse_bytes(bin.as_bytes(), 2)
+        } else if let Some(neg) = numeral.strip_prefix("(- ").and_then(|n| n.strip_suffix(")")) {
+            BigInt::parse_bytes(neg.as_bytes(), 10).map(|n| -n)
+        } else {
+            BigInt::parse_bytes(numeral.as_bytes(), 10)
+        }
+    }
+
+    fn as_bv_pair(
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> (z3::ast::BV<'ctx>, z3::ast::BV<'ctx>) {
+        (lhs.as_bv().expect("lhs is not bitvector"), rhs.as_bv().expect("rhs is not bitvector"))
+    }
 }
 
 impl<'ctx> SmtSolver<'ctx> for Z3Conv<'ctx> {
@@ -114,6 +137,19 @@ impl<'ctx> SmtSolver<'ctx> for Z3Conv<'ctx> {
             .expect("Wrong result")
     }
 
+    fn eval_value(&self, expr: Expr) -> Option<NString> {
+        let ast = self.get_cache_ast(&expr)?;
+        let value = self.z3_solver.get_model()?.eval(&ast, true)?.to_string();
+        let ty = expr.ty();
+        if ty.is_integer() {
+            // Bitvectors are unsigned in Z3
+            if let Some(n) = Z3Conv::parse_numeral(&value) {
+                return Some(ty.wrap_integer(n).to_string().into());
+            }
+        }
+        Some(value.into())
+    }
+
     fn show_model(&self) {
         match self.z3_solver.get_model() {
             Some(m) => println!("{m:?}"),
@@ -135,6 +171,10 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         self.cur_alloc_expr = Some(ast);
     }
 
+    fn int_encoding(&self) -> IntEncoding {
+        self.int_encoding
+    }
+
     fn convert_struct_sort(&mut self, ty: Type) -> z3::Sort<'ctx> {
         self.mk_struct_sort(ty)
     }
@@ -148,8 +188,11 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
     }
 
     fn convert_null(&self, ty: Type) -> z3::ast::Dynamic<'ctx> {
-        let null_pt =
-            self.mk_pointer(&self.mk_smt_int(BigInt::ZERO), &self.mk_smt_int(BigInt::ZERO), None);
+        let null_pt = self.mk_pointer(
+            &self.mk_smt_usize(BigInt::ZERO),
+            &self.mk_smt_usize(BigInt::ZERO),
+            None,
+        );
         assert!(ty.is_primitive_ptr());
         null_pt
     }
@@ -164,7 +207,7 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
     }
 
     fn convert_pointer_base(&self, pt: &z3::ast::Dynamic<'ctx>) -> z3::ast::Dynamic<'ctx> {
-        if pt.get_sort() == self.mk_int_sort() {
+        if pt.get_sort() == self.mk_usize_sort() {
             return pt.clone();
         }
         self.mk_pointer_base(pt)
@@ -225,10 +268,9 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         assert!(as_variant.is_as_variant());
         let idx = as_variant.extract_variant_idx();
         let i = bigint_to_usize(&field.extract_integer());
-        assert!(i == 0);
         let tuple = self.convert_ast(as_variant);
         let ty = object.ty().enum_variant_data_type(idx);
-        self.mk_tuple_select(tuple, 0, ty)
+        self.mk_tuple_select(tuple, i, ty)
     }
 
     fn convert_tuple_update(
@@ -284,6 +326,10 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         z3::Sort::int(&self.z3_ctx)
     }
 
+    fn mk_bv_sort(&self, width: u32) -> z3::Sort<'ctx> {
+        z3::Sort::bitvector(&self.z3_ctx, width)
+    }
+
     fn mk_array_sort(&mut self, domain: &z3::Sort<'ctx>, range: &z3::Sort<'ctx>) -> z3::Sort<'ctx> {
         z3::Sort::array(&self.z3_ctx, domain, range)
     }
@@ -303,6 +349,16 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         )
     }
 
+    fn mk_smt_bv(&self, i: BigInt, width: u32) -> z3::ast::Dynamic<'ctx> {
+        // Two's complement representation
+        let modulus = BigInt::from(1) << width;
+        let num = ((i % &modulus) + &modulus) % &modulus;
+        z3::ast::Dynamic::from(
+            z3::ast::BV::from_str(&self.z3_ctx, width, num.to_string().as_str())
+                .expect("Wrong bitvector"),
+        )
+    }
+
     fn mk_smt_const_array(
         &self,
         domain: &z3::Sort<'ctx>,
@@ -319,6 +375,10 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         z3::ast::Dynamic::from(z3::ast::Int::new_const(&self.z3_ctx, name.to_string()))
     }
 
+    fn mk_bv_symbol(&self, name: NString, width: u32) -> z3::ast::Dynamic<'ctx> {
+        z3::ast::Dynamic::from(z3::ast::BV::new_const(&self.z3_ctx, name.to_string(), width))
+    }
+
     fn mk_array_symbol(
         &self,
         name: NString,
@@ -367,6 +427,10 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         lhs: &z3::ast::Dynamic<'ctx>,
         rhs: &z3::ast::Dynamic<'ctx>,
     ) -> z3::ast::Dynamic<'ctx> {
+        if lhs.as_bv().is_some() {
+            let (l, r) = Self::as_bv_pair(lhs, rhs);
+            return z3::ast::Dynamic::from(l.bvadd(&r));
+        }
         z3::ast::Dynamic::from(
             lhs.as_int().expect("lhs is not integer") + rhs.as_int().expect("rhs is not integer"),
         )
@@ -377,6 +441,10 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         lhs: &z3::ast::Dynamic<'ctx>,
         rhs: &z3::ast::Dynamic<'ctx>,
     ) -> z3::ast::Dynamic<'ctx> {
+        if lhs.as_bv().is_some() {
+            let (l, r) = Self::as_bv_pair(lhs, rhs);
+            return z3::ast::Dynamic::from(l.bvsub(&r));
+        }
         z3::ast::Dynamic::from(
             lhs.as_int().expect("lhs is not integer") - rhs.as_int().expect("rhs is not integer"),
         )
@@ -387,6 +455,10 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         lhs: &z3::ast::Dynamic<'ctx>,
         rhs: &z3::ast::Dynamic<'ctx>,
     ) -> z3::ast::Dynamic<'ctx> {
+        if lhs.as_bv().is_some() {
+            let (l, r) = Self::as_bv_pair(lhs, rhs);
+            return z3::ast::Dynamic::from(l.bvmul(&r));
+        }
         z3::ast::Dynamic::from(
             lhs.as_int().expect("lhs is not integer") * rhs.as_int().expect("rhs is not integer"),
         )
@@ -397,6 +469,10 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         lhs: &z3::ast::Dynamic<'ctx>,
         rhs: &z3::ast::Dynamic<'ctx>,
     ) -> z3::ast::Dynamic<'ctx> {
+        if lhs.as_bv().is_some() {
+            let (l, r) = Self::as_bv_pair(lhs, rhs);
+            return z3::ast::Dynamic::from(l.bvudiv(&r));
+        }
         z3::ast::Dynamic::from(
             lhs.as_int().expect("lhs is not integer") / rhs.as_int().expect("rhs is not integer"),
         )
@@ -423,6 +499,9 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         lhs: &z3::ast::Dynamic<'ctx>,
         rhs: &z3::ast::Dynamic<'ctx>,
     ) -> z3::ast::Dynamic<'ctx> {
+        if lhs.as_bv().is_some() {
+            return self.mk_bvuge(lhs, rhs);
+        }
         z3::ast::Dynamic::from(
             lhs.as_int()
                 .expect("lhs is not integer")
@@ -435,6 +514,9 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         lhs: &z3::ast::Dynamic<'ctx>,
         rhs: &z3::ast::Dynamic<'ctx>,
     ) -> z3::ast::Dynamic<'ctx> {
+        if lhs.as_bv().is_some() {
+            return self.mk_bvugt(lhs, rhs);
+        }
         z3::ast::Dynamic::from(
             lhs.as_int()
                 .expect("lhs is not integer")
@@ -447,6 +529,9 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         lhs: &z3::ast::Dynamic<'ctx>,
         rhs: &z3::ast::Dynamic<'ctx>,
     ) -> z3::ast::Dynamic<'ctx> {
+        if lhs.as_bv().is_some() {
+            return self.mk_bvule(lhs, rhs);
+        }
         z3::ast::Dynamic::from(
             lhs.as_int()
                 .expect("lhs is not integer")
@@ -459,6 +544,9 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         lhs: &z3::ast::Dynamic<'ctx>,
         rhs: &z3::ast::Dynamic<'ctx>,
     ) -> z3::ast::Dynamic<'ctx> {
+        if lhs.as_bv().is_some() {
+            return self.mk_bvult(lhs, rhs);
+        }
         z3::ast::Dynamic::from(
             lhs.as_int()
                 .expect("lhs is not integer")
@@ -512,4 +600,196 @@ impl<'ctx> Convert<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
     ) -> z3::ast::Dynamic<'ctx> {
         cond.as_bool().expect("condition must be bool").ite(true_value, false_value)
     }
+
+    fn mk_bvsdiv(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvsdiv(&r))
+    }
+
+    fn mk_bvudiv(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvudiv(&r))
+    }
+
+    fn mk_bvsge(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvsge(&r))
+    }
+
+    fn mk_bvuge(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvuge(&r))
+    }
+
+    fn mk_bvsgt(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvsgt(&r))
+    }
+
+    fn mk_bvugt(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvugt(&r))
+    }
+
+    fn mk_bvsle(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvsle(&r))
+    }
+
+    fn mk_bvule(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvule(&r))
+    }
+
+    fn mk_bvslt(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvslt(&r))
+    }
+
+    fn mk_bvult(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvult(&r))
+    }
+
+    fn mk_bv_extract(
+        &self,
+        operand: &z3::ast::Dynamic<'ctx>,
+        high: u32,
+        low: u32,
+    ) -> z3::ast::Dynamic<'ctx> {
+        z3::ast::Dynamic::from(
+            operand.as_bv().expect("operand is not bitvector").extract(high, low),
+        )
+    }
+
+    fn mk_bv_sign_ext(&self, operand: &z3::ast::Dynamic<'ctx>, n: u32) -> z3::ast::Dynamic<'ctx> {
+        z3::ast::Dynamic::from(operand.as_bv().expect("operand is not bitvector").sign_ext(n))
+    }
+
+    fn mk_bv_zero_ext(&self, operand: &z3::ast::Dynamic<'ctx>, n: u32) -> z3::ast::Dynamic<'ctx> {
+        z3::ast::Dynamic::from(operand.as_bv().expect("operand is not bitvector").zero_ext(n))
+    }
+
+    fn mk_bvsrem(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvsrem(&r))
+    }
+
+    fn mk_bvurem(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvurem(&r))
+    }
+
+    fn mk_bvand(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvand(&r))
+    }
+
+    fn mk_bvor(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvor(&r))
+    }
+
+    fn mk_bvxor(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvxor(&r))
+    }
+
+    fn mk_bvshl(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvshl(&r))
+    }
+
+    fn mk_bvashr(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvashr(&r))
+    }
+
+    fn mk_bvlshr(
+        &self,
+        lhs: &z3::ast::Dynamic<'ctx>,
+        rhs: &z3::ast::Dynamic<'ctx>,
+    ) -> z3::ast::Dynamic<'ctx> {
+        let (l, r) = Self::as_bv_pair(lhs, rhs);
+        z3::ast::Dynamic::from(l.bvlshr(&r))
+    }
+
+    fn mk_int2bv(&self, operand: &z3::ast::Dynamic<'ctx>, width: u32) -> z3::ast::Dynamic<'ctx> {
+        // `int2bv` wraps the integer modulo 2^width, which keeps the lifting pure,
+        // i.e., no constraint is asserted out of the path guard.
+        let i = operand.as_int().expect("operand is not integer");
+        z3::ast::Dynamic::from(z3::ast::BV::from_int(&i, width))
+    }
+
+    fn mk_bv2int(&self, operand: &z3::ast::Dynamic<'ctx>, signed: bool) -> z3::ast::Dynamic<'ctx> {
+        z3::ast::Dynamic::from(operand.as_bv().expect("operand is not bitvector").to_int(signed))
+    }
 }
@@ -15,9 +15,9 @@ impl<'ctx> MemSpace<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
             .variant(
                 "pointer",
                 vec![
-                    ("base", DatatypeAccessor::Sort(z3::Sort::int(&self.z3_ctx))),
-                    ("offset", DatatypeAccessor::Sort(z3::Sort::int(&self.z3_ctx))),
-                    ("meta", DatatypeAccessor::Sort(z3::Sort::int(&self.z3_ctx))),
+                    ("base", DatatypeAccessor::Sort(self.mk_usize_sort())),
+                    ("offset", DatatypeAccessor::Sort(self.mk_usize_sort())),
+                    ("meta", DatatypeAccessor::Sort(self.mk_usize_sort())),
                 ],
             )
             .finish();
@@ -38,7 +38,7 @@ impl<'ctx> MemSpace<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
             self.init_pointer_space(object);
         }
         let ident = self.pointer_logic.get_object_space_ident(object);
-        self.mk_smt_int(BigInt::from(ident))
+        self.mk_smt_usize(BigInt::from(ident))
     }
 
     fn init_pointer_space(&mut self, object: &Expr) {
@@ -48,13 +48,15 @@ impl<'ctx> MemSpace<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
 
         // Use l0 as identifier. Object size is in byte-level
         let space_start = NString::from(object.extract_symbol().ident()) + "_base";
-        let start = self.mk_int_symbol(space_start);
-        let size = self.mk_smt_int(BigInt::from(ty.size()));
+        let start = self.mk_usize_symbol(space_start);
+        let size = self.mk_smt_usize(BigInt::from(ty.size()));
         let end = self.mk_add(&start, &size);
         // Start is greater than 0
-        self.assert(self.mk_gt(&start, &self.mk_smt_int(BigInt::ZERO)));
+        self.assert(self.mk_gt(&start, &self.mk_smt_usize(BigInt::ZERO)));
         // Size is greater or eqaul to 0
-        self.assert(self.mk_ge(&size, &self.mk_smt_int(BigInt::ZERO)));
+        self.assert(self.mk_ge(&size, &self.mk_smt_usize(BigInt::ZERO)));
+        // The space does not wrap around the address space
+        self.assert(self.mk_le(&start, &end));
         // Disjoint relationship
         for (j, (s, e)) in self.pointer_logic.object_spaces().values().enumerate() {
             if j == i {
@@ -68,7 +70,7 @@ impl<'ctx> MemSpace<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
             }
 
             let alloc_array_ast = self.cur_alloc_expr.as_ref().unwrap();
-            let ident = self.mk_smt_int(BigInt::from(j));
+            let ident = self.mk_smt_usize(BigInt::from(j));
             let alive = alloc_array_ast.as_array().unwrap().select(&ident);
 
             let no_overlap = self.mk_or(&self.mk_le(&end, &s), &self.mk_le(&e, &start));
@@ -87,7 +89,7 @@ impl<'ctx> MemSpace<z3::Sort<'ctx>, z3::ast::Dynamic<'ctx>> for Z3Conv<'ctx> {
         let sign = (NString::from("pointer"), vec![]);
         let metadata = match meta {
             Some(x) => x.clone(),
-            None => self.mk_smt_int(0.into()),
+            None => self.mk_smt_usize(0.into()),
         };
         self.datatypes.get(&sign).unwrap().variants[0].constructor.apply(&[
             base as &dyn Ast,
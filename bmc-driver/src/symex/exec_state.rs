@@ -7,6 +7,7 @@ use super::frame::*;
 use super::namespace::Namespace;
 use super::renaming::*;
 use super::state::*;
+use super::unsupported::unsupported;
 use super::value_set::ObjectSet;
 use crate::config::config::Config;
 use crate::expr::context::*;
@@ -50,7 +51,7 @@ impl<'cfg> ExecState<'cfg> {
         }
     }
 
-    pub fn setup(&mut self) {
+    pub fn setup(&mut self, entry: NString) {
         // create global variable
         let ty = Type::infinite_array_type(Type::bool_type());
         let ident = Ident::Global(NString::ALLOC_SYM);
@@ -58,7 +59,7 @@ impl<'cfg> ExecState<'cfg> {
         let alloc_array = self.ctx.object(alloc_array_symbol);
         self.ns.insert_object(alloc_array);
         // Initialized stack
-        let entry_function = self.config.program.function_id(self.config.cli.entry_function);
+        let entry_function = self.config.program.function_id(entry);
         self.push_frame(entry_function, None, None);
     }
 
@@ -77,6 +78,10 @@ impl<'cfg> ExecState<'cfg> {
         }
     }
 
+    pub fn frames(&self) -> &[Frame<'cfg>] {
+        &self.frames
+    }
+
     pub fn top(&self) -> &Frame<'cfg> {
         self.frames.last().expect("Empty frame stack")
     }
@@ -85,6 +90,11 @@ impl<'cfg> ExecState<'cfg> {
         self.frames.last_mut().expect("Empty frame stack")
     }
 
+    /// The number of frames of `function` on the call stack
+    pub fn recursion_depth(&self, function: NString) -> usize {
+        self.frames.iter().filter(|frame| frame.function.name() == function).count()
+    }
+
     pub fn reset_to_unexplored_state(&mut self) {
         if self.top().unexplored_states.is_empty() {
             panic!("We stuck in a loop, please increase the loop bound");
@@ -347,7 +357,7 @@ impl<'cfg> ExecState<'cfg> {
             return;
         }
 
-        panic!("Do not support place state: {place:?}");
+        unsupported!("place state of {place:?}");
     }
 
     pub fn assignment(&mut self, lhs: Expr, rhs: Expr) {
@@ -409,9 +419,11 @@ impl<'cfg> ExecState<'cfg> {
         // A variable `x` of type `Node` has three fields of form `x.data(<variant_idx>)`,
         // in value set, e.g. `x.0[1-0]`, `x.0[2-0]` and `x.0[2-1]`, where `0` denote the data field.
         if lhs.ty().is_enum() {
-            // Remove all possible fields firstly.
-            let prefix = NString::from(format!("{lhs:?}.data"));
-            self.remove_pointers_by(prefix);
+            // Remove all possible fields firstly, unless the values are joined
+            if !is_union {
+                let prefix = NString::from(format!("{lhs:?}.data"));
+                self.remove_pointers_by(prefix);
+            }
             // Do assignment
             if rhs.is_variant() || rhs.is_constant() {
                 let (data, i) = if rhs.is_variant() {
@@ -523,7 +535,7 @@ impl<'cfg> ExecState<'cfg> {
             return;
         }
 
-        todo!("{lhs:?}");
+        unsupported!("assignment to {lhs:?}");
     }
 
     pub(super) fn remove_pointers_by(&mut self, prefix: NString) {
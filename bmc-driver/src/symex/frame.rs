@@ -57,6 +57,15 @@ impl<'func> Frame<'func> {
         if c == l1_num && s { PlaceState::Own } else { PlaceState::Dead }
     }
 
+    /// The entry and the iteration of the innermost loop containing `pc`
+    pub fn innermost_loop(&self) -> Option<(Pc, usize)> {
+        self.loop_stack
+            .iter()
+            .rev()
+            .find(|&&(entry, _)| self.function.loop_blocks(entry).contains(&self.pc))
+            .copied()
+    }
+
     pub fn add_state(&mut self, pc: Pc, state: State) {
         self.unexplored_states.entry(pc).or_default().push(state);
     }
@@ -8,15 +8,20 @@ mod state;
 mod value_set;
 
 pub mod symex;
+pub(super) mod symex_arbitrary;
 pub(super) mod symex_assert;
 pub(super) mod symex_assign;
 pub(super) mod symex_branch;
 pub(super) mod symex_builtin;
 pub(super) mod symex_cast;
+pub(super) mod symex_contract;
 mod symex_core;
 pub(super) mod symex_drop;
 pub(super) mod symex_function;
+pub(super) mod symex_induction;
+pub(super) mod symex_invariant;
 pub(super) mod symex_memory;
 // pub(super) mod symex_move;
 mod symex_std;
 pub(super) mod symex_util;
+pub mod unsupported;
@@ -4,6 +4,7 @@ use stable_mir::ty::IndexedVal;
 
 use super::place_state::PlaceState;
 use super::symex::Symex;
+use super::unsupported::unsupported;
 use super::value_set::*;
 use crate::expr::context::ExprCtx;
 use crate::expr::expr::*;
@@ -58,7 +59,7 @@ impl<'a, 'cfg> Projection<'a, 'cfg> {
                     let idx = i.to_index();
                     self._ctx.as_variant(ret, self._ctx.constant_usize(idx))
                 }
-                _ => panic!("Not support {elem:?} for {ret:?}"),
+                _ => unsupported!("projection {elem:?} of {ret:?}"),
             };
         }
 
@@ -155,7 +156,7 @@ impl<'a, 'cfg> Projection<'a, 'cfg> {
             let offset = index.extract_constant().to_integer();
             self.build_with_const_offset(object, offset, elem_ty)
         } else {
-            todo!("Non-const index")
+            unsupported!("non-constant index {index:?}")
         }
     }
 
@@ -274,9 +275,8 @@ impl<'a, 'cfg> Projection<'a, 'cfg> {
             let mut error = guard.clone();
             error.add(out_of_bound);
             self._callback_symex.claim(msg, error.to_expr());
-        } else {
-            todo!();
         }
+        // Infinite arrays, e.g., the buffer of a `Vec`, are bounded by their owners.
         res
     }
 
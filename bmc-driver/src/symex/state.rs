@@ -3,6 +3,7 @@ use std::fmt::Debug;
 
 use super::place_state::*;
 use super::renaming::Renaming;
+use super::unsupported::unsupported;
 use super::value_set::*;
 use crate::expr::context::*;
 use crate::expr::expr::*;
@@ -233,7 +234,7 @@ impl State {
             return;
         }
 
-        panic!("Do not support dereferencing:\n{expr:?} with {suffix:?}");
+        unsupported!("dereferencing {expr:?} with {suffix:?}");
     }
 
     /// Get objects from current expr. Similar to get_reference_rec in ESBMC.
@@ -313,7 +314,7 @@ impl State {
             return;
         }
 
-        panic!("Do not support get object from: {expr:?}")
+        unsupported!("getting objects from {expr:?}")
     }
 }
 
@@ -1,9 +1,14 @@
+use std::collections::HashMap;
+use std::panic::{AssertUnwindSafe, catch_unwind, resume_unwind};
+
 use stable_mir::CrateDef;
 use stable_mir::mir::*;
 
 use super::exec_state::*;
 use super::frame::*;
+use super::unsupported::*;
 use crate::config::config::Config;
+use crate::config::unwind::Unwind;
 use crate::expr::context::*;
 use crate::expr::expr::*;
 use crate::expr::ty::*;
@@ -16,18 +21,38 @@ pub struct Symex<'cfg> {
     pub(super) config: &'cfg Config,
     pub(super) program: &'cfg Program,
     pub(super) ctx: ExprCtx,
+    /// The harness being verified
+    pub(super) entry: NString,
+    pub(super) unwind: Unwind,
+    /// The `k` of the inductive step of k-induction
+    pub(super) induction: Option<usize>,
     pub(super) exec_state: ExecState<'cfg>,
     pub(super) vc_system: VCSysPtr,
+    pub(super) unsupported: Vec<Unsupported>,
+    pub(super) havocked: Vec<NString>,
+    /// Upper bounds of the lengths of `vec`s, indexed by their buffers
+    pub(super) vec_max_lens: HashMap<Ident, usize>,
 }
 
 impl<'cfg> Symex<'cfg> {
-    pub fn new(config: &'cfg Config, vc_system: VCSysPtr) -> Self {
+    pub fn new(config: &'cfg Config, vc_system: VCSysPtr, entry: NString, unwind: Unwind) -> Self {
         let ctx = config.expr_ctx.clone();
         let mut exec_state = ExecState::new(config, ctx.clone());
-        exec_state.setup();
+        exec_state.setup(entry);
 
-        let mut symex =
-            Symex { config, program: &config.program, ctx: ctx.clone(), exec_state, vc_system };
+        let mut symex = Symex {
+            config,
+            program: &config.program,
+            ctx: ctx.clone(),
+            entry,
+            unwind,
+            induction: None,
+            exec_state,
+            vc_system,
+            unsupported: Vec::new(),
+            havocked: Vec::new(),
+            vec_max_lens: HashMap::new(),
+        };
         symex.init();
         symex
     }
@@ -54,10 +79,79 @@ impl<'cfg> Symex<'cfg> {
         let alloc_array = self.exec_state.ns.lookup_object(ident);
         let const_array = self.ctx.constant_array(self.ctx.constant_bool(false), None);
         self.assign(alloc_array, const_array, self.ctx._true().into());
+
+        if contract_of(self.entry).is_some_and(|(_, mode)| mode == ContractMode::Check) {
+            self.nondet_arguments();
+        }
     }
 
+    /// Unsupported constructs in statements and terminators only abandon
+    /// their paths. The others, e.g., in the memory leak check, stop symex.
     pub fn run(&mut self) {
-        self.symex();
+        if let Err(unsupported) = self.catch_unsupported(|symex| symex.symex()) {
+            self.add_unsupported(unsupported);
+        }
+    }
+
+    /// Unsupported constructs reached by symex
+    pub fn unsupported(&self) -> &Vec<Unsupported> {
+        &self.unsupported
+    }
+
+    /// Functions over-approximated in havoc mode
+    pub fn havocked(&self) -> &Vec<NString> {
+        &self.havocked
+    }
+
+    pub(super) fn record_unsupported(&mut self, construct: String) {
+        let unsupported = self.unsupported_at(construct);
+        self.add_unsupported(unsupported);
+    }
+
+    fn unsupported_at(&self, construct: String) -> Unsupported {
+        let function = self.exec_state.can_exec().then(|| self.top().function.name());
+        let span = self.exec_state.cur_span();
+        Unsupported { construct, span, function }
+    }
+
+    /// A construct reached by several paths is reported once
+    fn add_unsupported(&mut self, unsupported: Unsupported) {
+        if !self.unsupported.contains(&unsupported) {
+            self.unsupported.push(unsupported);
+        }
+    }
+
+    /// Turn the unsupported construct raised by `unsupported!` in `f` into
+    /// an error
+    fn catch_unsupported<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> Result<R, Unsupported> {
+        match catch_unwind(AssertUnwindSafe(|| f(self))) {
+            Ok(res) => Ok(res),
+            Err(payload) => match payload.downcast::<UnsupportedConstruct>() {
+                Ok(construct) => Err(self.unsupported_at(construct.0)),
+                Err(payload) => resume_unwind(payload),
+            },
+        }
+    }
+
+    /// The path can not be encoded. Report the construct and explore the
+    /// other paths.
+    fn abandon_path(&mut self, unsupported: Unsupported) {
+        self.add_unsupported(unsupported);
+        self.end_path();
+    }
+
+    /// Stop exploring the current path. Symex continues with a state waiting
+    /// in the frame. The frames without such states are only reached by this
+    /// path, and they are popped.
+    pub(super) fn end_path(&mut self) {
+        self.exec_state.cur_state.guard.make_false();
+        while self.exec_state.can_exec() && self.top().unexplored_states.is_empty() {
+            let frame = self.exec_state.pop_frame();
+            self.clear_locals(&frame);
+        }
+        if self.exec_state.can_exec() {
+            self.exec_state.reset_to_unexplored_state();
+        }
     }
 
     fn symex(&mut self) {
@@ -94,7 +188,10 @@ impl<'cfg> Symex<'cfg> {
         let function_name = self.top().function.name();
         for (i, statement) in bb.statements.iter().enumerate() {
             self.exec_state.update_span(statement.span);
-            self.symex_statement(statement);
+            if let Err(unsupported) = self.symex_statement(statement) {
+                self.abandon_path(unsupported);
+                return;
+            }
 
             if self.config.enable_display_state_statement()
                 && self.config.enable_display_state_in_function(function_name)
@@ -106,7 +203,14 @@ impl<'cfg> Symex<'cfg> {
             }
         }
 
-        let is_unwind = self.symex_terminator(&bb.terminator);
+        self.exec_state.update_span(bb.terminator.span);
+        let is_unwind = match self.symex_terminator(&bb.terminator) {
+            Ok(is_unwind) => is_unwind,
+            Err(unsupported) => {
+                self.abandon_path(unsupported);
+                return;
+            }
+        };
         if !is_unwind
             && self.config.enable_display_state_terminator()
             && self.config.enable_display_state_in_function(function_name)
@@ -115,13 +219,13 @@ impl<'cfg> Symex<'cfg> {
         }
     }
 
-    fn symex_statement(&mut self, statement: &Statement) {
-        match &statement.kind {
-            StatementKind::Assign(place, rvalue) => self.symex_assign(place, rvalue),
-            StatementKind::StorageLive(local) => self.symex_storagelive(*local),
-            StatementKind::StorageDead(local) => self.symex_storagedead(*local),
+    fn symex_statement(&mut self, statement: &Statement) -> Result<(), Unsupported> {
+        self.catch_unsupported(|symex| match &statement.kind {
+            StatementKind::Assign(place, rvalue) => symex.symex_assign(place, rvalue),
+            StatementKind::StorageLive(local) => symex.symex_storagelive(*local),
+            StatementKind::StorageDead(local) => symex.symex_storagedead(*local),
             _ => {}
-        }
+        })
     }
 
     fn symex_storagelive(&mut self, local: Local) {
@@ -139,14 +243,17 @@ impl<'cfg> Symex<'cfg> {
         self.exec_state.remove_pointers_by(ident);
     }
 
-    fn symex_terminator(&mut self, terminator: &Terminator) -> bool {
+    fn symex_terminator(&mut self, terminator: &Terminator) -> Result<bool, Unsupported> {
+        self.catch_unsupported(|symex| symex.symex_terminator_kind(&terminator.kind))
+    }
+
+    fn symex_terminator_kind(&mut self, kind: &TerminatorKind) -> bool {
         let mut is_unwind = false;
-        match &terminator.kind {
+        match kind {
             TerminatorKind::Goto { target } => self.symex_goto(*target),
             TerminatorKind::SwitchInt { discr, targets } => self.symex_switchint(discr, targets),
             TerminatorKind::Drop { place, target, .. } => {
-                self.symex_drop(place, target);
-                is_unwind = true;
+                is_unwind = self.symex_drop(place, target)
             }
             TerminatorKind::Call { func, args, destination, target, .. } => {
                 is_unwind = self.symex_call(func, args, destination, target)
@@ -155,7 +262,13 @@ impl<'cfg> Symex<'cfg> {
             TerminatorKind::Assert { cond, expected, msg, target, .. } => {
                 self.symex_assert(cond, expected, msg, target)
             }
-            _ => todo!(),
+            // Unreachable code, e.g., the otherwise branch of a `match` on an
+            // enum, must not be reached under the path guard.
+            TerminatorKind::Unreachable => {
+                self.claim("unreachable code reached".into(), self.ctx._true());
+                self.end_path();
+            }
+            kind => unsupported!("terminator {kind:?}"),
         };
         is_unwind
     }
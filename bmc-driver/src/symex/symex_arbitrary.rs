@@ -0,0 +1,214 @@
+use super::place_state::PlaceState;
+use super::symex::*;
+use super::unsupported::unsupported;
+use crate::config::cli::IntEncoding;
+use crate::expr::expr::*;
+use crate::expr::ty::Type;
+use crate::program::program::bigint_to_usize;
+
+/// Well-formed nondet values for `rbmc::nondet`, `rbmc::Arbitrary::any` and
+/// the bounded helpers, e.g., `rbmc::nondet_range`.
+/// A nondet symbol of an ADT type is unconstrained, e.g., a `Box` in it
+/// points to nothing. Thus, values are built by the structure of types.
+impl Symex<'_> {
+    /// Assign a well-formed nondet value to `lhs`
+    pub(super) fn arbitrary(&mut self, lhs: Expr) {
+        let ty = lhs.ty();
+        match self.arbitrary_value(ty, &mut Vec::new()) {
+            Some(value) => self.assign(lhs, value, self.ctx._true().into()),
+            None => unsupported!("arbitrary value of recursive type {ty:?}"),
+        }
+    }
+
+    /// `building` holds the types whose values are being built. A value of
+    /// them can not be built again, so that recursive types are cut off at
+    /// the first recursion, e.g., `Option<Box<Node>>` in a `Node` is `None`.
+    fn arbitrary_value(&mut self, ty: Type, building: &mut Vec<Type>) -> Option<Expr> {
+        if building.contains(&ty) {
+            return None;
+        }
+        building.push(ty);
+        let value = if ty.is_box() || (ty.is_ref() && !ty.is_slice_ptr()) {
+            self.arbitrary_pointer(ty, building)
+        } else if ty.is_slice_ptr() {
+            self.arbitrary_slice(ty, self.config.cli.nondet_len, building)
+        } else if ty.is_vec() {
+            self.arbitrary_vec(ty, self.config.cli.nondet_len, building)
+        } else if ty.is_float() {
+            unsupported!("arbitrary value of {ty:?}")
+        } else if ty.is_struct() || ty.is_tuple() {
+            let fields = if ty.is_struct() {
+                ty.struct_def().1.iter().map(|(_, ty)| *ty).collect::<Vec<_>>()
+            } else {
+                ty.tuple_def()
+            };
+            fields
+                .into_iter()
+                .map(|field| self.arbitrary_value(field, building))
+                .collect::<Option<Vec<_>>>()
+                .map(|fields| self.ctx.aggregate(fields, ty))
+        } else if ty.is_array() && ty.array_len().is_some() {
+            let elem_ty = ty.elem_type();
+            (0..ty.array_len().unwrap())
+                .map(|_| self.arbitrary_value(elem_ty, building))
+                .collect::<Option<Vec<_>>>()
+                .map(|elems| self.ctx.aggregate(elems, ty))
+        } else if ty.is_enum() {
+            self.arbitrary_enum(ty, building)
+        } else {
+            Some(self.arbitrary_symbol(ty))
+        };
+        building.pop();
+        value
+    }
+
+    /// A `Box` or a reference points to a fresh object, which is owned by it.
+    fn arbitrary_pointer(&mut self, ty: Type, building: &mut Vec<Type>) -> Option<Expr> {
+        let value = self.arbitrary_value(ty.pointee_ty(), building)?;
+        let object = self.arbitrary_object(value);
+        if ty.is_box() {
+            let address = self.ctx.address_of(object.clone(), object.extract_address_type());
+            Some(self.ctx._box(address))
+        } else {
+            Some(self.ctx.address_of(object, ty))
+        }
+    }
+
+    /// A fresh object holding `value`. It is owned by the pointer to it.
+    pub(crate) fn arbitrary_object(&mut self, value: Expr) -> Expr {
+        let object = self.exec_state.new_object(value.ty());
+        self.assign(object.clone(), value, self.ctx._true().into());
+        self.track_new_object(object.clone());
+        self.exec_state.update_place_state(object.clone(), PlaceState::Own);
+        object
+    }
+
+    /// One of the variants whose data can be built, chosen by a nondet index
+    fn arbitrary_enum(&mut self, ty: Type, building: &mut Vec<Type>) -> Option<Expr> {
+        let mut variants = Vec::new();
+        for i in 0..ty.enum_variants() {
+            let idx = self.ctx.constant_usize(i);
+            let data_ty = ty.enum_variant_data_type(i);
+            if data_ty.is_zero_sized_type() {
+                variants.push(self.ctx.constant_adt(vec![idx.extract_constant()], ty));
+            } else if let Some(data) = self.arbitrary_value(data_ty, building) {
+                variants.push(self.ctx.variant(idx, data, ty));
+            }
+        }
+        let mut value = variants.pop()?;
+        if variants.is_empty() {
+            return Some(value);
+        }
+        let choice = self.nondet_symbol(Type::usize_type());
+        for (i, variant) in variants.into_iter().enumerate().rev() {
+            let cond = self.ctx.eq(choice.clone(), self.ctx.constant_usize(i));
+            value = self.ctx.ite(cond, variant, value);
+        }
+        Some(value)
+    }
+
+    /// A nondet symbol. Integers are kept in the bounds of their types.
+    fn arbitrary_symbol(&mut self, ty: Type) -> Expr {
+        let symbol = self.nondet_symbol(ty);
+        if ty.is_integer() && self.config.cli.int_encoding == IntEncoding::Int {
+            let mut cond = self.integer_bounds(symbol.clone());
+            self.rename(&mut cond);
+            self.assume(cond);
+        }
+        symbol
+    }
+
+    /// `rbmc::nondet_range(lo, hi)`, a value in `lo..hi`
+    pub(super) fn symex_nondet_range(&mut self, lo: Expr, hi: Expr, lhs: Expr) {
+        let ty = lhs.ty();
+        if !ty.is_integer() {
+            unsupported!("rbmc::nondet_range of {ty:?}");
+        }
+        let msg = "empty range of rbmc::nondet_range";
+        self.claim(msg.into(), self.ctx.ge(lo.clone(), hi.clone()));
+        let value = self.arbitrary_symbol(ty);
+        self.assign(lhs, value.clone(), self.ctx._true().into());
+        let lower = self.ctx.le(lo, value.clone());
+        let upper = self.ctx.lt(value, hi);
+        self.assume_on_path(self.ctx.and(lower, upper));
+    }
+
+    /// A slice pointing to a fresh array of `max_len` arbitrary elements,
+    /// whose length is at most `max_len`
+    fn arbitrary_slice(
+        &mut self,
+        ty: Type,
+        max_len: usize,
+        building: &mut Vec<Type>,
+    ) -> Option<Expr> {
+        let array_ty = Type::array_type(ty.pointee_ty().elem_type(), max_len as u64);
+        let value = self.arbitrary_value(array_ty, building)?;
+        let object = self.arbitrary_object(value);
+        let address = self.ctx.address_of(object.clone(), object.extract_address_type());
+        let len = self.arbitrary_len(max_len);
+        Some(self.ctx.pointer(address, Some(len), ty))
+    }
+
+    /// A vector owning a fresh buffer of `max_len` arbitrary elements, whose
+    /// length is at most `max_len`
+    fn arbitrary_vec(
+        &mut self,
+        ty: Type,
+        max_len: usize,
+        building: &mut Vec<Type>,
+    ) -> Option<Expr> {
+        let elem_ty = ty.vec_elem_type();
+        let elems = (0..max_len)
+            .map(|_| self.arbitrary_value(elem_ty, building))
+            .collect::<Option<Vec<_>>>()?;
+        let buffer = self.new_vec_buffer(ty, max_len);
+        for (i, value) in elems.into_iter().enumerate() {
+            let elem = self.ctx.index(buffer.clone(), self.ctx.constant_usize(i), elem_ty);
+            self.assign(elem, value, self.ctx._true().into());
+        }
+        let address = self.ctx.address_of(buffer.clone(), buffer.extract_address_type());
+        let len = self.arbitrary_len(max_len);
+        Some(self.ctx._vec(address, len, self.ctx.constant_usize(max_len), ty))
+    }
+
+    /// A nondet length at most `max_len`
+    fn arbitrary_len(&mut self, max_len: usize) -> Expr {
+        let len = self.arbitrary_symbol(Type::usize_type());
+        let mut cond = self.ctx.le(len.clone(), self.ctx.constant_usize(max_len));
+        self.rename(&mut cond);
+        self.assume(cond);
+        len
+    }
+
+    /// `rbmc::nondet_slice(max_len)`, a slice of at most `max_len` arbitrary
+    /// elements
+    pub(super) fn symex_nondet_slice(&mut self, max_len: Expr, lhs: Expr) {
+        let ty = lhs.ty();
+        let max_len = self.max_len(max_len, "rbmc::nondet_slice");
+        let Some(slice) = self.arbitrary_slice(ty, max_len, &mut Vec::new()) else {
+            unsupported!("arbitrary value of recursive type {ty:?}")
+        };
+        self.assign(lhs, slice, self.ctx._true().into());
+    }
+
+    /// `rbmc::nondet_vec(max_len)`, a vector of at most `max_len` arbitrary
+    /// elements
+    pub(super) fn symex_nondet_vec(&mut self, max_len: Expr, lhs: Expr) {
+        let ty = lhs.ty();
+        let max_len = self.max_len(max_len, "rbmc::nondet_vec");
+        let Some(_vec) = self.arbitrary_vec(ty, max_len, &mut Vec::new()) else {
+            unsupported!("arbitrary value of recursive type {ty:?}")
+        };
+        self.assign(lhs, _vec, self.ctx._true().into());
+    }
+
+    /// The maximal length of nondet slices and vectors must be a constant
+    fn max_len(&mut self, mut max_len: Expr, function: &str) -> usize {
+        self.rename(&mut max_len);
+        max_len.simplify();
+        if !max_len.is_constant() {
+            unsupported!("non-constant length of {function}");
+        }
+        bigint_to_usize(&max_len.extract_constant().to_integer())
+    }
+}
@@ -1,8 +1,11 @@
+use num_bigint::BigInt;
+use stable_mir::mir;
 use stable_mir::mir::*;
 
 use super::symex::*;
 use crate::expr::expr::*;
 use crate::symbol::nstring::NString;
+use crate::vc::vc::CoverKind;
 
 impl<'cfg> Symex<'cfg> {
     pub(super) fn symex_assert(
@@ -12,24 +15,117 @@ impl<'cfg> Symex<'cfg> {
         msg: &AssertMessage,
         target: &usize,
     ) {
-        let msg = NString::from("built-in check: ") + msg.description().unwrap();
+        // Division and shift checks are claimed while encoding the operators.
+        // Overflow checks of `+`, `-`, `*` and negation are generated by rustc
+        // and named after the operand type.
+        let msg = match msg {
+            AssertMessage::DivisionByZero(..) | AssertMessage::RemainderByZero(..) => None,
+            AssertMessage::Overflow(
+                mir::BinOp::Div | mir::BinOp::Rem | mir::BinOp::Shl | mir::BinOp::Shr,
+                ..,
+            ) => None,
+            AssertMessage::Overflow(mir::BinOp::Add, lhs, _) => self.overflow_message("add", lhs),
+            AssertMessage::Overflow(mir::BinOp::Sub, lhs, _) => self.overflow_message("sub", lhs),
+            AssertMessage::Overflow(mir::BinOp::Mul, lhs, _) => self.overflow_message("mul", lhs),
+            AssertMessage::OverflowNeg(operand) => self.overflow_message("neg", operand),
+            _ => Some(NString::from("built-in check: ") + msg.description().unwrap()),
+        };
 
-        let expr = self.make_operand(cond);
-
-        let mut cond = expr.clone();
-        self.replace_predicates(&mut cond);
-        self.rename(&mut cond);
-        // Make assert fail and continue check other assertions
-        if *expected == true {
-            cond = self.ctx.not(cond);
+        if let Some(msg) = msg {
+            let mut error = self.make_operand(cond);
+            // Make assert fail and continue check other assertions
+            if *expected == true {
+                error = self.ctx.not(error);
+            }
+            self.claim(msg, error);
         }
-        self.vc_system.borrow_mut().assert(msg, cond, self.exec_state.cur_span());
 
         if self.top().pc + 1 == *target {
             self.top_mut().pc += 1;
         } else {
             let state = self.exec_state.cur_state.clone();
             self.cache_unexplored_state(*target, state);
+            self.exec_state.reset_to_unexplored_state();
+        }
+    }
+
+    /// `rbmc::assert(cond, msg)`. The message is a string literal.
+    pub(super) fn symex_rbmc_assert(&mut self, cond: Expr, msg: &Operand) {
+        let msg = match self.make_str_literal(msg) {
+            Some(msg) => NString::from(msg),
+            None => NString::from("assertion rbmc::assert"),
+        };
+        if self.config.cli.assertion_reach_checks {
+            self.cover(CoverKind::Reach, msg, self.ctx._true());
+        }
+        self.claim(msg, self.ctx.not(cond));
+    }
+
+    /// `rbmc::cover(cond, msg)`. The message is a string literal.
+    pub(super) fn symex_rbmc_cover(&mut self, cond: Expr, msg: &Operand) {
+        let msg = match self.make_str_literal(msg) {
+            Some(msg) => NString::from(msg),
+            None => NString::from("cover rbmc::cover"),
+        };
+        self.cover(CoverKind::Cover, msg, cond);
+    }
+
+    /// The message of an overflow check on `operand`, unless overflow checks
+    /// are turned off
+    fn overflow_message(&self, name: &str, operand: &Operand) -> Option<NString> {
+        if self.config.cli.no_overflow_checks {
+            return None;
+        }
+        let ty = self.top().function.operand_type(operand);
+        Some(NString::from(format!("arithmetic overflow on {ty:?} {name}")))
+    }
+
+    /// Claim that the divisor of `lhs / rhs` or `lhs % rhs` is not zero, and that
+    /// `MIN / -1` does not happen for signed integers.
+    pub(super) fn check_division(&mut self, bop: mir::BinOp, lhs: Expr, rhs: Expr) {
+        let name = match bop {
+            mir::BinOp::Div => "div",
+            mir::BinOp::Rem => "rem",
+            _ => unreachable!("Do not check division for {bop:?}"),
+        };
+        let ty = lhs.ty();
+
+        let msg = NString::from(format!("division by zero on {ty:?} {name}"));
+        let error = self.ctx.eq(rhs.clone(), self.ctx.constant_integer(0.into(), ty));
+        self.claim(msg, error);
+
+        if ty.is_signed() {
+            let msg = NString::from(format!("arithmetic overflow on {ty:?} {name}"));
+            let is_min = self.ctx.eq(lhs, self.ctx.constant_integer(ty.min_value(), ty));
+            let is_minus_one = self.ctx.eq(rhs, self.ctx.constant_integer((-1).into(), ty));
+            self.claim(msg, self.ctx.and(is_min, is_minus_one));
+        }
+    }
+
+    /// Claim that the shift amount is less than the bit width of `lhs`. Unchecked
+    /// shifts are always checked since overflowing them is undefined behavior.
+    pub(super) fn check_shift(&mut self, bop: mir::BinOp, lhs: Expr, rhs: Expr) {
+        let name = match bop {
+            mir::BinOp::Shl | mir::BinOp::ShlUnchecked => "shl",
+            mir::BinOp::Shr | mir::BinOp::ShrUnchecked => "shr",
+            _ => unreachable!("Do not check shift for {bop:?}"),
+        };
+        let is_unchecked = matches!(bop, mir::BinOp::ShlUnchecked | mir::BinOp::ShrUnchecked);
+        if self.config.cli.no_overflow_checks && !is_unchecked {
+            return;
+        }
+
+        let ty = rhs.ty();
+        let bits = BigInt::from(lhs.ty().size() * 8);
+        let mut error = self.ctx._false();
+        if ty.is_signed() {
+            error = self.ctx.lt(rhs.clone(), self.ctx.constant_integer(BigInt::ZERO, ty));
+        }
+        if bits <= ty.max_value() {
+            let too_large = self.ctx.ge(rhs, self.ctx.constant_integer(bits, ty));
+            error = self.ctx.or(error, too_large);
         }
+        let msg = NString::from(format!("arithmetic overflow on {:?} {name}", lhs.ty()));
+        self.claim(msg, error);
     }
 }
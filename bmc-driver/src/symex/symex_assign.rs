@@ -4,6 +4,7 @@ use stable_mir::ty::IndexedVal;
 use stable_mir::ty::TyConst;
 
 use super::symex::*;
+use super::unsupported::unsupported;
 use crate::expr::expr::*;
 use crate::expr::guard::*;
 use crate::expr::ty::*;
@@ -23,6 +24,9 @@ impl<'cfg> Symex<'cfg> {
 
     pub(super) fn assign(&mut self, lhs: Expr, rhs: Expr, guard: Guard) {
         assert!(lhs.ty().is_layout() || lhs.ty() == rhs.ty());
+        if lhs.ty().is_float() {
+            unsupported!("floating-point value {rhs:?}");
+        }
         self.assign_rec(lhs.clone(), rhs.clone(), guard);
     }
 
@@ -98,7 +102,7 @@ impl<'cfg> Symex<'cfg> {
             return;
         }
 
-        panic!("Do not support assignment:\n{lhs:?} = {rhs:?}");
+        unsupported!("assignment {lhs:?} = {rhs:?}");
     }
 
     fn assign_ite(&mut self, lhs: Expr, rhs: Expr, guard: Guard) {
@@ -125,15 +129,16 @@ impl<'cfg> Symex<'cfg> {
         match rvalue {
             Rvalue::AddressOf(_, place) => self.make_address_of(place, ty),
             Rvalue::Aggregate(k, operands) => self.make_aggregate(k, operands, ty),
-            Rvalue::BinaryOp(bop, lop, rop) => self.make_binary(*bop, lop, rop),
+            Rvalue::BinaryOp(bop, lop, rop) => self.make_binary(*bop, lop, rop, ty),
+            Rvalue::CheckedBinaryOp(bop, lop, rop) => self.make_checked_binary(*bop, lop, rop, ty),
             Rvalue::UnaryOp(uop, operand) => self.make_unary(*uop, operand),
             Rvalue::Cast(k, operand, ty) => self.symex_cast(*k, operand, Type::from(ty)),
             Rvalue::Ref(_, _, place) => self.make_address_of(place, ty),
             Rvalue::NullaryOp(nop, t) => self.make_nullary(nop.clone(), t.into()),
             Rvalue::Use(operand) => self.make_operand(operand),
             Rvalue::Repeat(operand, tyconst) => self.make_repeat(operand, tyconst),
-            Rvalue::Discriminant(place) => self.make_discriminant(place),
-            _ => todo!("{rvalue:?}"),
+            Rvalue::Discriminant(place) => self.make_discriminant(place, ty),
+            _ => unsupported!("rvalue {rvalue:?}"),
         }
     }
 
@@ -187,19 +192,36 @@ impl<'cfg> Symex<'cfg> {
                 let meta = args[1].clone();
                 self.ctx.pointer(address, Some(meta), ty)
             }
-            _ => todo!("{k:?}"),
+            _ => unsupported!("aggregate {k:?}"),
         }
     }
 
-    fn make_binary(&mut self, bop: mir::BinOp, lop: &Operand, rop: &Operand) -> Expr {
-        let op = BinOp::from(bop);
+    fn make_binary(&mut self, bop: mir::BinOp, lop: &Operand, rop: &Operand, ty: Type) -> Expr {
         let lhs = self.make_operand(lop);
         let rhs = self.make_operand(rop);
+        if matches!(bop, mir::BinOp::Div | mir::BinOp::Rem) {
+            self.check_division(bop, lhs.clone(), rhs.clone());
+        }
+        let op = BinOp::from(bop);
+        if matches!(op, BinOp::Shl | BinOp::Shr) {
+            self.check_shift(bop, lhs.clone(), rhs.clone());
+        }
         match op {
             BinOp::Add => self.ctx.add(lhs, rhs),
             BinOp::Sub => self.ctx.sub(lhs, rhs),
             BinOp::Mul => self.ctx.mul(lhs, rhs),
             BinOp::Div => self.ctx.div(lhs, rhs),
+            BinOp::Rem => self.ctx.rem(lhs, rhs),
+            // `&`, `|` and `^` on booleans are logical operators
+            BinOp::BitAnd if lhs.ty().is_bool() => self.ctx.and(lhs, rhs),
+            BinOp::BitOr if lhs.ty().is_bool() => self.ctx.or(lhs, rhs),
+            BinOp::BitXor if lhs.ty().is_bool() => self.ctx.ne(lhs, rhs),
+            BinOp::BitAnd => self.ctx.bit_and(lhs, rhs),
+            BinOp::BitOr => self.ctx.bit_or(lhs, rhs),
+            BinOp::BitXor => self.ctx.bit_xor(lhs, rhs),
+            BinOp::Shl => self.ctx.shl(lhs, rhs),
+            BinOp::Shr => self.ctx.shr(lhs, rhs),
+            BinOp::Cmp => self.ctx.cmp(lhs, rhs, ty),
             BinOp::Eq => self.ctx.eq(lhs, rhs),
             BinOp::Ne => self.ctx.ne(lhs, rhs),
             BinOp::Ge => self.ctx.ge(lhs, rhs),
@@ -213,6 +235,29 @@ impl<'cfg> Symex<'cfg> {
         }
     }
 
+    /// `AddWithOverflow`, `SubWithOverflow` and `MulWithOverflow` return the
+    /// result and whether the operation overflows. Their overflow checks are
+    /// the `Assert` terminators after them.
+    fn make_checked_binary(
+        &mut self,
+        bop: mir::BinOp,
+        lop: &Operand,
+        rop: &Operand,
+        ty: Type,
+    ) -> Expr {
+        let lhs = self.make_operand(lop);
+        let rhs = self.make_operand(rop);
+        let op = BinOp::from(bop);
+        let result = match op {
+            BinOp::Add => self.ctx.add(lhs.clone(), rhs.clone()),
+            BinOp::Sub => self.ctx.sub(lhs.clone(), rhs.clone()),
+            BinOp::Mul => self.ctx.mul(lhs.clone(), rhs.clone()),
+            _ => unsupported!("checked operator {bop:?}"),
+        };
+        let overflow = self.ctx.overflow(op, lhs, rhs);
+        self.ctx.aggregate(vec![result, overflow], ty)
+    }
+
     fn make_unary(&mut self, uop: mir::UnOp, operand: &Operand) -> Expr {
         let op = UnOp::from(uop);
         let operand = self.make_operand(operand);
@@ -226,7 +271,7 @@ impl<'cfg> Symex<'cfg> {
     fn make_nullary(&mut self, nop: NullOp, ty: Type) -> Expr {
         match nop {
             NullOp::UbChecks | NullOp::ContractChecks => self.ctx._false(),
-            _ => todo!(),
+            _ => unsupported!("nullary operator {nop:?}"),
         }
     }
 
@@ -239,15 +284,15 @@ impl<'cfg> Symex<'cfg> {
         self.ctx.constant_array(value, Some(len))
     }
 
-    fn make_discriminant(&mut self, place: &Place) -> Expr {
+    fn make_discriminant(&mut self, place: &Place, ty: Type) -> Expr {
         let expr = self.make_project(place);
         assert!(expr.ty().is_enum());
-        let def = expr.ty().enum_def();
-        let mut discr = self.ctx.constant_isize(0);
-        for i in 1..def.1.len() {
-            let idx = self.ctx.constant_isize(i as isize);
-            let cond = self.ctx.match_variant(expr.clone(), idx.clone());
-            discr = self.ctx.ite(cond, idx, discr);
+        let discriminants = expr.ty().enum_discriminants();
+        let mut discr = self.ctx.constant_integer(discriminants[0].clone(), ty);
+        for (i, value) in discriminants.into_iter().enumerate().skip(1) {
+            let cond = self.ctx.match_variant(expr.clone(), self.ctx.constant_isize(i as isize));
+            let value = self.ctx.constant_integer(value, ty);
+            discr = self.ctx.ite(cond, value, discr);
         }
         discr
     }
@@ -3,6 +3,7 @@ use stable_mir::mir::*;
 
 use super::state::State;
 use super::symex::*;
+use super::unsupported::unsupported;
 use crate::expr::expr::*;
 use crate::expr::ty::Type;
 use crate::program::function::Pc;
@@ -23,9 +24,20 @@ impl<'cfg> Symex<'cfg> {
             if self.get_unwind(target) {
                 // Loop bound exceed
                 self.top_mut().loop_stack.pop();
-                let mut cond = self.exec_state.cur_state.guard.to_expr();
-                cond = self.ctx.not(cond);
-                self.assume(cond);
+                if self.induction.is_some() {
+                    // The inductive step only needs `k + 1` iterations. The
+                    // path is not assumed away, which would hide the
+                    // properties checked in the last iteration.
+                } else if self.unwind.assertions {
+                    // The bound is not enough if the back-edge is reachable
+                    let function = self.top().function.name();
+                    let msg = format!("unwinding assertion loop bb{target} in {function:?}");
+                    self.claim(msg.into(), self.ctx._true());
+                } else {
+                    let mut cond = self.exec_state.cur_state.guard.to_expr();
+                    cond = self.ctx.not(cond);
+                    self.assume(cond);
+                }
                 self.exec_state.reset_to_unexplored_state();
             } else {
                 // Keep unwinding
@@ -37,7 +49,18 @@ impl<'cfg> Symex<'cfg> {
     fn get_unwind(&mut self, pc: Pc) -> bool {
         let (l, count) = self.top().loop_stack.last().unwrap();
         assert!(pc == *l);
-        self.config.cli.unwind != 0 && *count >= self.config.cli.unwind
+        let function = self.top().function;
+        // The back-edge
+        let span = function.basicblock(self.top().pc).terminator.span;
+        let bound = match self.induction {
+            // The invariant is checked in the third iteration
+            _ if self.has_loop_invariant(pc) => 3,
+            Some(k) => k + 1,
+            None => {
+                self.config.loop_bound(self.entry, function.name(), pc, span, self.unwind.bound)
+            }
+        };
+        bound != 0 && *count >= bound
     }
 
     pub(super) fn symex_switchint(&mut self, discr: &Operand, targets: &SwitchTargets) {
@@ -76,10 +99,10 @@ impl<'cfg> Symex<'cfg> {
 
     fn make_branch_guard(&mut self, discr_expr: Expr, i: u128) -> Expr {
         if discr_expr.ty().is_integer() {
-            let mut discr = self.ctx.eq(
-                discr_expr.clone(),
-                self.ctx.constant_integer(BigInt::from(i), discr_expr.ty()),
-            );
+            // The value is the bit pattern of the discriminant
+            let ty = discr_expr.ty();
+            let value = self.ctx.constant_integer(ty.wrap_integer(BigInt::from(i)), ty);
+            let mut discr = self.ctx.eq(discr_expr.clone(), value);
             self.rename(&mut discr);
             discr.simplify();
             if discr.is_constant() || discr.is_symbol() {
@@ -94,7 +117,7 @@ impl<'cfg> Symex<'cfg> {
         } else if discr_expr.ty().is_bool() {
             if i == 0 { self.ctx.not(discr_expr) } else { discr_expr }
         } else {
-            panic!("Not support for this type of SwitchInt")
+            unsupported!("SwitchInt on {:?}", discr_expr.ty())
         }
     }
 
@@ -2,6 +2,7 @@ use num_bigint::BigInt;
 use stable_mir::mir::*;
 
 use super::symex::*;
+use super::unsupported::unsupported;
 use crate::expr::expr::*;
 use crate::expr::ty::*;
 
@@ -14,7 +15,7 @@ impl<'cfg> Symex<'cfg> {
             CastKind::IntToInt => self.symex_cast_inttoint(expr, ty),
             CastKind::PtrToPtr => self.symex_cast_ptrtoptr(expr, ty),
             CastKind::Transmute => self.symex_cast_transmute(expr, ty),
-            _ => todo!("{kind:?} - {expr:?} -> {ty:?}"),
+            _ => unsupported!("cast {kind:?} from {:?} to {ty:?}", expr.ty()),
         }
     }
 
@@ -32,7 +33,7 @@ impl<'cfg> Symex<'cfg> {
         let src_ty = pt.ty();
         match coercion {
             PointerCoercion::MutToConstPointer | PointerCoercion::ArrayToPointer => {
-                todo!("Support later")
+                unsupported!("pointer coercion {coercion:?}")
             }
             PointerCoercion::Unsize => {
                 if src_ty.pointee_ty().is_array() && target_ty.is_slice_ptr() {
@@ -41,10 +42,10 @@ impl<'cfg> Symex<'cfg> {
                     let meta = self.ctx.constant_usize(len);
                     self.ctx.pointer(address, Some(meta), target_ty)
                 } else {
-                    todo!("{src_ty:?} => {target_ty:?}")
+                    unsupported!("unsize coercion from {src_ty:?} to {target_ty:?}")
                 }
             }
-            _ => todo!("Unsupport function pointer"),
+            _ => unsupported!("pointer coercion {coercion:?}"),
         }
     }
 
@@ -0,0 +1,60 @@
+use super::symex::*;
+use super::unsupported::unsupported;
+use crate::expr::expr::*;
+use crate::program::program::*;
+use crate::symbol::nstring::NString;
+
+impl Symex<'_> {
+    /// With `--replace-contracts`, calls to a function with a contract go to
+    /// the function replacing it, except the call checking the contract.
+    pub(super) fn contract_replacement(&self, function: NString) -> NString {
+        if !self.config.cli.replace_contracts || !self.program.has_contract(function) {
+            return function;
+        }
+        let caller = self.top().function.name();
+        if caller == contract_wrapper(function, ContractMode::Check) {
+            return function;
+        }
+        contract_wrapper(function, ContractMode::Replace)
+    }
+
+    /// The arguments of the function checking a contract are arbitrary. A
+    /// reference points to a fresh object owned by the caller.
+    pub(super) fn nondet_arguments(&mut self) {
+        for &i in self.top().function.args().iter() {
+            let lhs = self.exec_state.l0_local(i);
+            let ty = lhs.ty();
+            if ty.is_ptr() {
+                unsupported!("argument of type {ty:?} in contracts");
+            }
+            self.arbitrary(lhs);
+        }
+    }
+
+    fn contract_mode(&self, clause: &str) -> (NString, ContractMode) {
+        match contract_of(self.top().function.name()) {
+            Some(contract) => contract,
+            None => unsupported!("rbmc::contract::{clause} out of contracts"),
+        }
+    }
+
+    pub(super) fn symex_requires(&mut self, cond: Expr) {
+        match self.contract_mode("requires") {
+            (_, ContractMode::Check) => self.assume_on_path(cond),
+            (function, ContractMode::Replace) => {
+                let msg = format!("precondition of {function:?}");
+                self.claim(msg.into(), self.ctx.not(cond));
+            }
+        }
+    }
+
+    pub(super) fn symex_ensures(&mut self, cond: Expr) {
+        match self.contract_mode("ensures") {
+            (function, ContractMode::Check) => {
+                let msg = format!("postcondition of {function:?}");
+                self.claim(msg.into(), self.ctx.not(cond));
+            }
+            (_, ContractMode::Replace) => self.assume_on_path(cond),
+        }
+    }
+}
@@ -1,10 +1,14 @@
+mod symex_str;
+
 use stable_mir::CrateDef;
 use stable_mir::mir::mono::Instance;
 
 use crate::expr::expr::*;
 use crate::expr::ty::*;
 use crate::symbol::nstring::*;
+use crate::symex::projection::Mode;
 use crate::symex::symex::*;
+use crate::symex::unsupported::unsupported;
 
 /// This mod defines symbolic execution of api in core
 ///
@@ -18,9 +22,54 @@ impl<'cfg> Symex<'cfg> {
             || name == "slice_end_index_len_fail"
         {
             self.symex_slice_assertion(name);
+        } else if name.starts_with("core::slice::<impl [T]>::get".into()) {
+            self.symex_slice_get(instance, args, dest);
+        } else if name.starts_with("core::str".into()) {
+            self.symex_str_api(name, args, dest);
         } else {
-            panic!("Not support {name:?}");
+            self.unsupported_function(name, args, dest);
+        }
+    }
+
+    /// `get` and `get_mut` with an `usize` index
+    fn symex_slice_get(&mut self, instance: Instance, args: Vec<Expr>, dest: Expr) {
+        if !args[1].ty().is_usize() {
+            unsupported!("{:?} with {:?}", instance.name(), args[1].ty());
+        }
+        let pt = args[0].clone();
+        let len = self.ctx.pointer_meta(pt.clone());
+        let i = args[1].clone();
+        let slice =
+            self.make_deref(pt.clone(), Mode::Read, self.ctx._true().into(), pt.ty().pointee_ty());
+        if !slice.is_slice() {
+            unsupported!("{:?} of {slice:?}", instance.name());
         }
+
+        // Return `None` if `i` is out of bounds
+        let ty = dest.ty();
+        let none = self.ctx.constant_adt(vec![self.ctx.constant_usize(0).extract_constant()], ty);
+        let some = if self.is_out_of_bounds(i.clone(), len.clone()) {
+            none.clone()
+        } else {
+            // Borrow the element in the array being sliced
+            let array = slice.extract_object();
+            let j = self.ctx.add(slice.extract_slice_start(), i.clone());
+            let pointer_ty = ty.enum_variant_data_type(1);
+            let address = self.borrow_elem(array, j, pointer_ty.field_type(0));
+            let data = self.ctx.aggregate(vec![address], pointer_ty);
+            self.ctx.variant(self.ctx.constant_usize(1), data, ty)
+        };
+        let rhs = self.ctx.ite(self.ctx.lt(i, len), some, none);
+        self.assign(dest, rhs, self.ctx._true().into());
+    }
+
+    /// Whether `i >= len` is known during symex
+    fn is_out_of_bounds(&mut self, i: Expr, len: Expr) -> bool {
+        let mut cond = self.ctx.ge(i, len);
+        self.replace_predicates(&mut cond);
+        self.rename(&mut cond);
+        cond.simplify();
+        cond.is_true()
     }
 
     fn symex_slice_assertion(&mut self, fname: NString) {
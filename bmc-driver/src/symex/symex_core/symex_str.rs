@@ -0,0 +1,135 @@
+use stable_mir::mir::Mutability;
+use stable_mir::ty::UintTy;
+
+use crate::expr::expr::*;
+use crate::expr::ty::*;
+use crate::program::program::bigint_to_usize;
+use crate::symbol::nstring::*;
+use crate::symex::projection::Mode;
+use crate::symex::symex::*;
+use crate::symex::unsupported::unsupported;
+
+/// This mod defines symbolic execution of api in core::str.
+/// In our memory model, `str` is a slice of bytes. Thus, `&str` is a slice
+/// pointer whose meta is the length in bytes.
+impl<'cfg> Symex<'cfg> {
+    pub fn symex_str_api(&mut self, name: NString, args: Vec<Expr>, dest: Expr) {
+        if name == "core::str::<impl str>::len" {
+            let len = self.ctx.pointer_meta(args[0].clone());
+            self.assign(dest, len, self.ctx._true().into());
+        } else if name == "core::str::<impl str>::is_empty" {
+            let len = self.ctx.pointer_meta(args[0].clone());
+            let is_empty = self.ctx.eq(len, self.ctx.constant_usize(0));
+            self.assign(dest, is_empty, self.ctx._true().into());
+        } else if name == "core::str::<impl str>::as_bytes" {
+            let pt = args[0].clone();
+            let len = self.ctx.pointer_meta(pt.clone());
+            let bytes = self.ctx.pointer(pt, Some(len), dest.ty());
+            self.assign(dest, bytes, self.ctx._true().into());
+        } else if name.contains("Index".into()) {
+            self.symex_str_index(args[0].clone(), args[1].clone(), dest);
+        } else if name.contains("PartialEq".into()) {
+            let is_eq = self.str_eq(args[0].clone(), args[1].clone());
+            let rhs = if name.as_str().ends_with("::ne") { self.ctx.not(is_eq) } else { is_eq };
+            self.assign(dest, rhs, self.ctx._true().into());
+        } else {
+            self.unsupported_function(name, args, dest);
+        }
+    }
+
+    /// Slicing a `str` by a byte range. Both ends of the range must be in
+    /// bounds and lie on char boundaries.
+    pub(crate) fn symex_str_index(&mut self, pt: Expr, range: Expr, dest: Expr) {
+        let len = self.ctx.pointer_meta(pt.clone());
+        let usize_ty = Type::usize_type();
+        let field = |i| self.ctx.index(range.clone(), self.ctx.constant_usize(i), usize_ty);
+        let (start, end) = match range.ty().name().as_str() {
+            "Range" => (field(0), field(1)),
+            "RangeFrom" => (field(0), len.clone()),
+            "RangeTo" => (self.ctx.constant_usize(0), field(0)),
+            "RangeFull" => (self.ctx.constant_usize(0), len.clone()),
+            _ => unsupported!("slicing str by {:?}", range.ty()),
+        };
+        let msg = NString::from("str failure: byte index out of bounds");
+        let error = self
+            .ctx
+            .or(self.ctx.gt(start.clone(), end.clone()), self.ctx.gt(end.clone(), len.clone()));
+        self.claim(msg, error);
+
+        let slice =
+            self.make_deref(pt.clone(), Mode::Read, self.ctx._true().into(), pt.ty().pointee_ty());
+        let msg = NString::from("str failure: byte index is not a char boundary");
+        let is_boundary = self.ctx.and(
+            self.is_char_boundary(slice.clone(), start.clone(), len.clone()),
+            self.is_char_boundary(slice.clone(), end.clone(), len),
+        );
+        self.claim(msg, self.ctx.not(is_boundary));
+
+        // The sub slice starts at a byte of the array being sliced
+        if !slice.is_slice() {
+            unsupported!("slicing {slice:?}");
+        }
+        let array = slice.extract_object();
+        let i = self.ctx.add(slice.extract_slice_start(), start.clone());
+        let byte_ty = Type::unsigned_type(UintTy::U8);
+        let byte = self.elem_of_constant_index(array, i, byte_ty);
+        let address = self.ctx.address_of(byte, Type::ptr_type(byte_ty, Mutability::Not));
+        let sub_str = self.ctx.pointer(address, Some(self.ctx.sub(end, start)), dest.ty());
+        self.assign(dest, sub_str, self.ctx._true().into());
+    }
+
+    /// A byte index is a char boundary if it is not in the middle of a UTF-8
+    /// sequence, i.e., the byte is not of the form `0b10xxxxxx`.
+    fn is_char_boundary(&self, slice: Expr, i: Expr, len: Expr) -> Expr {
+        let byte_ty = Type::unsigned_type(UintTy::U8);
+        let byte = self.ctx.index(slice, i.clone(), byte_ty);
+        let is_ascii = self.ctx.lt(byte.clone(), self.ctx.constant_integer(128.into(), byte_ty));
+        let is_leading = self.ctx.ge(byte, self.ctx.constant_integer(192.into(), byte_ty));
+        let is_end =
+            self.ctx.or(self.ctx.eq(i.clone(), self.ctx.constant_usize(0)), self.ctx.ge(i, len));
+        self.ctx.or(is_end, self.ctx.or(is_ascii, is_leading))
+    }
+
+    /// Whether the bytes of two `str` are equal. The bytes are compared one by
+    /// one, thus one of the lengths must be a constant.
+    pub(crate) fn str_eq(&mut self, lhs: Expr, rhs: Expr) -> Expr {
+        let lhs_len = self.ctx.pointer_meta(lhs.clone());
+        let rhs_len = self.ctx.pointer_meta(rhs.clone());
+        let n = match self.constant_len(lhs_len.clone()).or(self.constant_len(rhs_len.clone())) {
+            Some(n) => n,
+            None => unsupported!("str comparison with non-constant lengths"),
+        };
+
+        let lhs_str = self.make_deref(
+            lhs.clone(),
+            Mode::Read,
+            self.ctx._true().into(),
+            lhs.ty().pointee_ty(),
+        );
+        let rhs_str = self.make_deref(
+            rhs.clone(),
+            Mode::Read,
+            self.ctx._true().into(),
+            rhs.ty().pointee_ty(),
+        );
+        let byte_ty = Type::unsigned_type(UintTy::U8);
+        let mut is_eq = self.ctx.eq(lhs_len.clone(), rhs_len);
+        for k in 0..n {
+            let i = self.ctx.constant_usize(k);
+            let lhs_byte = self.ctx.index(lhs_str.clone(), i.clone(), byte_ty);
+            let rhs_byte = self.ctx.index(rhs_str.clone(), i.clone(), byte_ty);
+            let is_byte_eq =
+                self.ctx.or(self.ctx.ge(i, lhs_len.clone()), self.ctx.eq(lhs_byte, rhs_byte));
+            is_eq = self.ctx.and(is_eq, is_byte_eq);
+        }
+        is_eq
+    }
+
+    /// The length if it is a constant after constant propagation
+    fn constant_len(&mut self, mut len: Expr) -> Option<usize> {
+        self.replace_predicates(&mut len);
+        self.rename(&mut len);
+        len.simplify();
+        len.is_constant().then(|| bigint_to_usize(&len.extract_constant().to_integer()))
+    }
+}
@@ -5,13 +5,27 @@ use crate::expr::expr::*;
 use crate::expr::ty::*;
 
 impl<'cfg> Symex<'cfg> {
-    pub(super) fn symex_drop(&mut self, place: &Place, target: &BasicBlockIdx) {
-        // Drop recursively
+    /// Return whether the drop glue is unwound
+    pub(super) fn symex_drop(&mut self, place: &Place, target: &BasicBlockIdx) -> bool {
         let place = self.make_project(place);
+        // `Vec` is executed by its semantic instead of the drop glue
+        if place.ty().is_vec() {
+            self.symex_vec_drop(place);
+            self.symex_goto(*target);
+            return false;
+        }
+
+        // Drop recursively
         let drop_instance = place.ty().drop_instance();
         let object = if place.is_object() { place } else { self.ctx.object(place) };
         let address =
             self.ctx.address_of(object.clone(), Type::ptr_type(object.ty(), Mutability::Mut));
-        self.symex_function(drop_instance, vec![address], None, &Some(*target));
+        self.symex_function(
+            drop_instance.trimmed_name().into(),
+            vec![address],
+            None,
+            &Some(*target),
+        );
+        true
     }
 }
@@ -2,7 +2,9 @@ use stable_mir::mir::mono::Instance;
 use stable_mir::mir::*;
 
 use super::frame::Frame;
+use super::projection::Mode;
 use super::symex::*;
+use crate::config::cli::IntEncoding;
 use crate::expr::expr::*;
 use crate::expr::ty::Type;
 use crate::symbol::nstring::NString;
@@ -19,58 +21,207 @@ impl<'cfg> Symex<'cfg> {
         target: &Option<BasicBlockIdx>,
     ) -> bool {
         let instance = self.top_mut().function.operand_type(func).function_instance();
-        let ty = Type::from(instance.ty());
+        let (name, ty) = match self.program.stub(&instance) {
+            Some(stub) => self.stub_function(stub),
+            None => (NString::from(instance.trimmed_name()), Type::from(instance.ty())),
+        };
 
-        let args_exprs = args.iter().map(|x| self.make_operand(x)).collect::<Vec<_>>();
+        let is_rbmc_assert = ty.is_rbmc_assert();
+        let is_rbmc_cover = ty.is_rbmc_cover();
+        // The message of `rbmc::assert` and `rbmc::cover` is not an expression
+        let n = if is_rbmc_assert || is_rbmc_cover { 1 } else { args.len() };
+        let args_exprs = args[..n].iter().map(|x| self.make_operand(x)).collect::<Vec<_>>();
 
-        let is_rbmc_nondet = ty.is_rbmc_nondet();
+        let is_rbmc_nondet =
+            ty.is_rbmc_nondet() || ty.is_rbmc_arbitrary() || ty.is_rbmc_nondet_array();
+        let is_nondet_range = ty.is_rbmc_nondet_range();
+        let is_nondet_slice = ty.is_rbmc_nondet_slice();
+        let is_nondet_vec = ty.is_rbmc_nondet_vec();
+        let is_rbmc_assume = ty.is_rbmc_assume();
+        let is_loop_invariant = ty.is_rbmc_loop_invariant();
+        let is_requires = ty.is_rbmc_requires();
+        let is_ensures = ty.is_rbmc_ensures();
         let is_rust_builtin = ty.is_rust_builtin_function();
+        let name = self.contract_replacement(name);
+        let has_body = self.program.contains_function(name);
+        let is_bounded = has_body && self.recursion_bound_exceeded(name);
 
         if is_rbmc_nondet {
             self.symex_nondet(dest);
+        } else if is_nondet_range {
+            let lhs = self.make_project(dest);
+            self.symex_nondet_range(args_exprs[0].clone(), args_exprs[1].clone(), lhs);
+        } else if is_nondet_slice {
+            let lhs = self.make_project(dest);
+            self.symex_nondet_slice(args_exprs[0].clone(), lhs);
+        } else if is_nondet_vec {
+            let lhs = self.make_project(dest);
+            self.symex_nondet_vec(args_exprs[0].clone(), lhs);
+        } else if is_rbmc_assume {
+            self.assume_on_path(args_exprs[0].clone());
+        } else if is_rbmc_assert {
+            self.symex_rbmc_assert(args_exprs[0].clone(), &args[1]);
+        } else if is_rbmc_cover {
+            self.symex_rbmc_cover(args_exprs[0].clone(), &args[1]);
+        } else if is_loop_invariant {
+            self.symex_loop_invariant(args_exprs[0].clone());
+        } else if is_requires {
+            self.symex_requires(args_exprs[0].clone());
+        } else if is_ensures {
+            self.symex_ensures(args_exprs[0].clone());
         } else if is_rust_builtin {
             self.symex_rust_builtin_function(instance, args_exprs, dest);
-        } else {
+        } else if is_bounded {
+            self.symex_recursion_bound(name);
+        } else if has_body {
             // Unwinding function
-            self.symex_function(instance, args_exprs, Some(dest.clone()), target);
+            self.symex_function(name, args_exprs, Some(dest.clone()), target);
+        } else {
+            // Functions without bodies, e.g., foreign functions
+            let ret = self.make_project(dest);
+            self.unsupported_function(name, args_exprs, ret);
         }
 
-        if is_rbmc_nondet || is_rust_builtin {
-            self.top_mut().pc += 1;
+        let is_unwind = !ty.is_builtin_function() && has_body && !is_bounded;
+        // A call exceeding the recursion bound has ended the path
+        if !is_unwind && !is_bounded {
+            match target {
+                Some(target) => self.symex_goto(*target),
+                None => self.top_mut().pc += 1,
+            }
         }
 
-        !is_rbmc_nondet && !is_rust_builtin
+        is_unwind
+    }
+
+    /// Calls to `instance` go to `stub` in the local crate. Its signature is
+    /// checked by `Program`.
+    fn stub_function(&self, stub: NString) -> (NString, Type) {
+        let function = self.program.function(self.program.function_id(stub));
+        (stub, function.ty())
+    }
+
+    fn recursion_bound_exceeded(&self, function: NString) -> bool {
+        let bound = self.config.recursion_bound(function, self.unwind.recursion_bound);
+        bound != 0 && self.exec_state.recursion_depth(function) >= bound
+    }
+
+    /// The call is not unwound. Like loops, the path is cut off, or checked to
+    /// be unreachable with `--unwinding-assertions`, and it ends here.
+    fn symex_recursion_bound(&mut self, function: NString) {
+        if self.unwind.assertions {
+            let msg = format!("unwinding assertion recursion in {function:?}");
+            self.claim(msg.into(), self.ctx._true());
+        } else {
+            let mut cond = self.exec_state.cur_state.guard.to_expr();
+            cond = self.ctx.not(cond);
+            self.assume(cond);
+        }
+        self.end_path();
     }
 
     fn symex_nondet(&mut self, dest: &Place) {
         let lhs = self.make_project(dest);
-        let n = self.exec_state.ns.lookup_nondet_count(lhs.ty());
-        let ident = Ident::Global(NString::from(format!("nondet_{:?}_{n}", lhs.ty())));
+        self.arbitrary(lhs);
+    }
+
+    /// A fresh nondet symbol of `ty`
+    pub(super) fn nondet_symbol(&mut self, ty: Type) -> Expr {
+        let n = self.exec_state.ns.lookup_nondet_count(ty);
+        let ident = Ident::Global(NString::from(format!("nondet_{ty:?}_{n}")));
         let symbol = Symbol::from(ident);
-        let nondet = self.ctx.mk_symbol(symbol, lhs.ty());
-        self.assign(lhs, nondet, self.ctx._true().into());
+        self.ctx.mk_symbol(symbol, ty)
+    }
+
+    /// Assign a fresh nondet value to `lhs`
+    pub(super) fn nondet(&mut self, lhs: Expr) {
+        let nondet = self.nondet_symbol(lhs.ty());
+        self.assign(lhs.clone(), nondet, self.ctx._true().into());
+        if self.config.cli.int_encoding == IntEncoding::Int {
+            // Mathematical integers are unbounded. Keep the value in the bounds of its type.
+            let mut cond = self.integer_bounds(lhs);
+            self.rename(&mut cond);
+            self.assume(cond);
+        }
+    }
+
+    /// The integers in `expr`, including the fields of structs, are in the
+    /// bounds of their types
+    pub(super) fn integer_bounds(&self, expr: Expr) -> Expr {
+        let ty = expr.ty();
+        if ty.is_integer() {
+            let lower = self.ctx.le(self.ctx.constant_integer(ty.min_value(), ty), expr.clone());
+            let upper = self.ctx.le(expr, self.ctx.constant_integer(ty.max_value(), ty));
+            self.ctx.and(lower, upper)
+        } else if ty.is_struct() {
+            let def = ty.struct_def();
+            let mut cond = self.ctx._true();
+            for (i, (_, field_ty)) in def.1.iter().enumerate() {
+                let field =
+                    self.ctx.index(expr.clone(), self.ctx.constant_isize(i as isize), *field_ty);
+                cond = self.ctx.and(cond, self.integer_bounds(field));
+            }
+            cond
+        } else {
+            self.ctx._true()
+        }
+    }
+
+    /// Symex goes on after an unsupported function. In havoc mode, the
+    /// function returns a nondet value. Otherwise, it is reported and the
+    /// destination of the call is left unconstrained to find more
+    /// unsupported constructs.
+    pub(super) fn unsupported_function(&mut self, name: NString, args: Vec<Expr>, dest: Expr) {
+        if self.config.cli.havoc {
+            self.havoc_function(name, args, dest);
+        } else {
+            self.record_unsupported(format!("function {name:?}"));
+        }
+    }
+
+    /// Over-approximate the call. The return value and the pointees of
+    /// mutable pointer arguments become nondet. Pointers stored in the
+    /// pointees are not followed.
+    fn havoc_function(&mut self, name: NString, args: Vec<Expr>, dest: Expr) {
+        if !self.havocked.contains(&name) {
+            self.havocked.push(name);
+        }
+        self.nondet(dest);
+        for arg in args {
+            let ty = arg.ty();
+            if !ty.is_mut_ptr() || ty.is_slice_ptr() {
+                continue;
+            }
+            let pointee =
+                self.make_deref(arg, Mode::Read, self.ctx._true().into(), ty.pointee_ty());
+            self.nondet(pointee);
+        }
     }
 
     fn symex_rust_builtin_function(&mut self, instance: Instance, args: Vec<Expr>, dest: &Place) {
         let name = NString::from(instance.name());
         let ret = self.make_project(dest);
-        if name.starts_with("std".into()) {
+        // Methods of trait implementations are named by `<Type as Trait>::method`
+        if name.starts_with("std".into())
+            || name.starts_with("<std".into())
+            || name.starts_with("<str".into())
+        {
             self.symex_std_api(instance, args, ret);
         } else if name.starts_with("core".into()) {
             self.symex_core_api(instance, args, ret);
         } else {
-            panic!("Do not support {name:?}")
+            self.unsupported_function(name, args, ret);
         }
     }
 
     pub fn symex_function(
         &mut self,
-        instance: Instance,
+        name: NString,
         args: Vec<Expr>,
         dest: Option<Place>,
         target: &Option<BasicBlockIdx>,
     ) {
-        let i = self.program.function_id(instance.trimmed_name().into());
+        let i = self.program.function_id(name);
         self.exec_state.push_frame(i, dest, *target);
         // Set arguements
         let parameters = self.top_mut().function.args();
@@ -132,7 +283,7 @@ impl<'cfg> Symex<'cfg> {
     }
 
     /// Remove symbols in `ns`, `renaming` and local pointers in value set.
-    fn clear_locals(&mut self, frame: &Frame) {
+    pub(super) fn clear_locals(&mut self, frame: &Frame) {
         for local in 0..frame.function.locals().len() {
             let ident = frame.local_ident(local);
             // Clear name space
@@ -0,0 +1,65 @@
+use stable_mir::CrateDef;
+
+use super::symex::*;
+use super::unsupported::unsupported;
+use crate::program::function::Pc;
+use crate::symbol::symbol::Ident;
+
+impl Symex<'_> {
+    /// Symex the inductive step of k-induction instead of the base case
+    pub fn set_induction(&mut self, k: usize) {
+        self.induction = Some(k);
+    }
+
+    /// The iteration of the innermost loop containing the current statement.
+    /// Calls in the body of a loop are in the loop.
+    pub(super) fn loop_iteration(&self) -> Option<usize> {
+        self.exec_state
+            .frames()
+            .iter()
+            .rev()
+            .find_map(|frame| frame.innermost_loop())
+            .map(|(_, n)| n)
+    }
+
+    /// In the inductive step, properties are assumed in the first `k`
+    /// iterations of a loop and checked in the next one.
+    pub(super) fn is_induction_hypothesis(&self) -> bool {
+        self.induction.is_some_and(|k| self.loop_iteration().is_some_and(|n| n <= k))
+    }
+
+    /// The inductive step starts from an arbitrary iteration of the loop.
+    /// The places written in the loop are havocked when it is entered.
+    pub(super) fn havoc_loop(&mut self, entry: Pc) {
+        if self.loop_iteration().is_some() {
+            unsupported!("k-induction of nested loop bb{entry}");
+        }
+        self.havoc_writes(entry, "k-induction");
+    }
+
+    /// Havoc the locals, the pointees and the statics written in the loop
+    /// at `entry`, which is handled by `feature`.
+    pub(super) fn havoc_writes(&mut self, entry: Pc, feature: &str) {
+        let function = self.top().function;
+        let writes = match self.program.loop_writes(function, entry) {
+            Ok(writes) => writes,
+            Err(write) => unsupported!("{feature} of loop bb{entry} with {write}"),
+        };
+        for place in writes.places {
+            // Locals in the body are initialized in each iteration
+            if place.projection.is_empty() && !self.top().local_states[place.local].1 {
+                continue;
+            }
+            let lhs = self.make_project(&place);
+            if lhs.ty().is_slice() {
+                unsupported!("{feature} of loop bb{entry} with write through {place:?}");
+            }
+            self.nondet(lhs);
+        }
+        for def in writes.statics {
+            let ident = Ident::Global(def.trimmed_name().into());
+            let object = self.exec_state.ns.lookup_object(ident);
+            self.nondet(object);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use super::symex::*;
+use super::unsupported::unsupported;
+use crate::expr::expr::*;
+use crate::program::function::Pc;
+
+impl Symex<'_> {
+    /// A loop with an invariant runs three iterations. The first one starts
+    /// from the entry state and checks the invariant. The second one starts
+    /// from an arbitrary state where the invariant holds, and the third one
+    /// checks the invariant again. Then the loop is done.
+    pub(super) fn symex_loop_invariant(&mut self, inv: Expr) {
+        let pc = self.top().pc;
+        let Some((entry, n)) = self.top().innermost_loop() else {
+            unsupported!("loop invariant out of loops");
+        };
+        if self.top().function.loop_invariant(entry) != Some(pc) {
+            unsupported!("more than one loop invariant in loop bb{entry}");
+        }
+        // The havocked iteration leaving the loop before the invariant is
+        // assumed would reach the code after the loop from any state
+        if self.top().function.loop_exits_before(entry, pc) {
+            unsupported!("loop bb{entry} exiting before its loop invariant");
+        }
+        let function = self.top().function.name();
+        match n {
+            1 => {
+                let msg = format!("loop invariant on entry of loop bb{entry} in {function:?}");
+                self.claim(msg.into(), self.ctx.not(inv));
+            }
+            2 => self.assume_on_path(inv),
+            _ => {
+                let msg = format!("loop invariant preserved by loop bb{entry} in {function:?}");
+                self.claim(msg.into(), self.ctx.not(inv));
+                // The remaining iterations are covered by the second one
+                self.exec_state.cur_state.guard.make_false();
+            }
+        }
+    }
+
+    pub(super) fn has_loop_invariant(&self, entry: Pc) -> bool {
+        self.top().function.loop_invariant(entry).is_some()
+    }
+}
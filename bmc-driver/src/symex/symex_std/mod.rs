@@ -1,6 +1,8 @@
 pub(super) mod symex_alloc;
 pub(super) mod symex_boxed;
 pub(super) mod symex_ptr;
+pub(super) mod symex_string;
+pub(super) mod symex_vec;
 
 use stable_mir::CrateDef;
 use stable_mir::mir::mono::Instance;
@@ -10,6 +12,7 @@ use crate::expr::expr::*;
 use crate::expr::ty::*;
 use crate::symbol::nstring::*;
 use crate::symex::symex::*;
+use crate::symex::unsupported::unsupported;
 
 /// This mod defines symbolic execution of api in std
 impl<'cfg> Symex<'cfg> {
@@ -22,8 +25,16 @@ impl<'cfg> Symex<'cfg> {
             self.symex_boxed_api(instance, args, dest);
         } else if name.starts_with("std::ptr".into()) {
             self.symex_ptr_api(instance, args, dest);
+        } else if name.starts_with("std::vec".into()) || name.starts_with("<std::vec".into()) {
+            self.symex_vec_api(instance, args, dest);
+        } else if name.starts_with("std::str".into())
+            || name.starts_with("<std::string".into())
+            || name.starts_with("<str".into())
+        {
+            // `std::str` is a prefix of `std::string`
+            self.symex_string_api(instance, args, dest);
         } else {
-            panic!("Not support {name:?}");
+            self.unsupported_function(name, args, dest);
         }
     }
 
@@ -34,7 +45,7 @@ impl<'cfg> Symex<'cfg> {
         } else if name == "Box::<T, A>::into_raw" {
             self.symex_box_into_raw(ret);
         } else {
-            todo!("Not implement");
+            unsupported!("special semantic of {name:?}");
         }
     }
 }
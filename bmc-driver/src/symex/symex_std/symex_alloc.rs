@@ -22,7 +22,7 @@ impl<'cfg> Symex<'cfg> {
         } else if name.starts_with("Layout".into()) {
             self.symex_layout_api(instance, args, dest);
         } else {
-            panic!("Not support {name:?}");
+            self.unsupported_function(name, args, dest);
         }
     }
 
@@ -51,8 +51,13 @@ impl<'cfg> Symex<'cfg> {
         self.rename(&mut layout);
         let ty = layout.extract_type();
         assert!(pt.ty().is_ptr());
+        self.dealloc_pointer(pt, Mode::Dealloc, ty);
+    }
+
+    /// Dealloc the objects `pt` points to. `ty` is the layout of the objects.
+    pub(super) fn dealloc_pointer(&mut self, pt: Expr, mode: Mode, ty: Type) {
         // Generate assertions
-        self.make_deref(pt.clone(), Mode::Dealloc, self.ctx._true().into(), ty);
+        self.make_deref(pt.clone(), mode, self.ctx._true().into(), ty);
 
         self.exec_state.cur_state.dealloc_objects(pt.clone());
         self.exec_state.cur_state.remove_pointer(pt.clone());
@@ -87,7 +92,7 @@ impl<'cfg> Symex<'cfg> {
                 self.symex_layout_align(dest, ty_expr.extract_type());
             }
         } else {
-            todo!("{name:?}");
+            self.unsupported_function(name, args, dest);
         }
     }
 
@@ -22,7 +22,7 @@ impl<'cfg> Symex<'cfg> {
         if name == "Box::<T>::new" {
             self.symex_box_new(dest, args);
         } else {
-            panic!("Not support {name:?}");
+            self.unsupported_function(name, args, dest);
         }
     }
 
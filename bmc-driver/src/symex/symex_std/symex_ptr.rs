@@ -22,7 +22,7 @@ impl<'cfg> Symex<'cfg> {
         } else if name == "std::ptr::mut_ptr::<impl *mut T>::is_null" {
             self.symex_ptr_is_null(dest, args);
         } else {
-            panic!("Not support for {name:?}");
+            self.unsupported_function(name, args, dest);
         }
     }
 
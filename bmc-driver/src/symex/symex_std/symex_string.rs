@@ -0,0 +1,108 @@
+use stable_mir::CrateDef;
+use stable_mir::mir::Mutability;
+use stable_mir::mir::mono::Instance;
+
+use super::super::symex::*;
+use crate::expr::expr::*;
+use crate::expr::ty::*;
+use crate::symbol::nstring::*;
+use crate::symex::projection::Mode;
+
+/// This mod defines symbolic execution of api in std::string.
+/// In our memory model, `String` wraps a `vec` of bytes, and its api is
+/// executed by the semantic of `vec`. The bytes are viewed as `str`.
+impl<'cfg> Symex<'cfg> {
+    pub fn symex_string_api(&mut self, instance: Instance, args: Vec<Expr>, dest: Expr) {
+        let fty = Type::from(instance.ty());
+        let name = NString::from(fty.fn_def().0.trimmed_name());
+        if name == "String::new" {
+            self.symex_string_new(dest, self.ctx.constant_usize(0));
+        } else if name == "String::with_capacity" {
+            self.symex_string_new(dest, args[0].clone());
+        } else if name == "String::push_str" {
+            let _vec = self.string_vec(args[0].clone());
+            self.extend_vec(_vec, args[1].clone());
+        } else if name == "String::len" {
+            let _vec = self.string_vec(args[0].clone());
+            self.assign(dest, self.ctx.vec_len(_vec), self.ctx._true().into());
+        } else if name == "String::capacity" {
+            let _vec = self.string_vec(args[0].clone());
+            self.assign(dest, self.ctx.vec_cap(_vec), self.ctx._true().into());
+        } else if name == "String::is_empty" {
+            let _vec = self.string_vec(args[0].clone());
+            let is_empty = self.ctx.eq(self.ctx.vec_len(_vec), self.ctx.constant_usize(0));
+            self.assign(dest, is_empty, self.ctx._true().into());
+        } else if name == "String::clear" {
+            let _vec = self.string_vec(args[0].clone());
+            let cap = self.ctx.vec_cap(_vec.clone());
+            self.update_vec(_vec, self.ctx.constant_usize(0), cap);
+        } else if name == "String::as_bytes"
+            || name == "String::as_str"
+            || name.contains("Deref".into())
+        {
+            let _vec = self.string_vec(args[0].clone());
+            let slice = self.vec_as_slice(_vec, dest.ty());
+            self.assign(dest, slice, self.ctx._true().into());
+        } else if name.contains("Index".into()) {
+            let pt = self.string_as_str(args[0].clone());
+            self.symex_str_index(pt, args[1].clone(), dest);
+        } else if name.contains("PartialEq".into()) {
+            let is_eq = self.string_eq(args);
+            let rhs = if name.as_str().ends_with("::ne") { self.ctx.not(is_eq) } else { is_eq };
+            self.assign(dest, rhs, self.ctx._true().into());
+        } else if name.contains("From".into())
+            || name.contains("ToOwned".into())
+            || name.contains("ToString".into())
+        {
+            self.symex_string_from(args[0].clone(), dest);
+        } else {
+            self.unsupported_function(name, args, dest);
+        }
+    }
+
+    fn symex_string_new(&mut self, dest: Expr, cap: Expr) {
+        let lhs = dest.clone();
+        let vec_ty = lhs.ty().field_type(0);
+        let _vec = self.new_vec(vec_ty, cap);
+        let string = self.ctx.aggregate(vec![_vec], lhs.ty());
+        self.assign(lhs, string, self.ctx._true().into());
+    }
+
+    /// `String::from`, `to_owned` and `to_string` copy a `str` to a new
+    /// `String`, whose capacity is its length.
+    fn symex_string_from(&mut self, pt: Expr, dest: Expr) {
+        let len = self.ctx.pointer_meta(pt.clone());
+        self.symex_string_new(dest.clone(), len);
+        let _vec =
+            self.ctx.index(dest.clone(), self.ctx.constant_usize(0), dest.ty().field_type(0));
+        self.extend_vec(_vec, pt);
+    }
+
+    /// `String == String`, `String == str` and `String == &str`
+    fn string_eq(&mut self, args: Vec<Expr>) -> Expr {
+        let lhs = self.string_as_str(args[0].clone());
+        let rhs_ty = args[1].ty().pointee_ty();
+        let rhs = if rhs_ty.is_string() {
+            self.string_as_str(args[1].clone())
+        } else if rhs_ty.is_str() {
+            args[1].clone()
+        } else {
+            self.make_deref(args[1].clone(), Mode::Read, self.ctx._true().into(), rhs_ty)
+        };
+        self.str_eq(lhs, rhs)
+    }
+
+    /// The `vec` that the `String` `pt` points to wraps
+    fn string_vec(&mut self, pt: Expr) -> Expr {
+        let ty = pt.ty().pointee_ty();
+        let string = self.make_deref(pt, Mode::Read, self.ctx._true().into(), ty);
+        self.ctx.index(string, self.ctx.constant_usize(0), ty.field_type(0))
+    }
+
+    /// View the `String` `pt` points to as `&str`
+    fn string_as_str(&mut self, pt: Expr) -> Expr {
+        let _vec = self.string_vec(pt);
+        let ty = Type::ptr_type(Type::str_type(), Mutability::Not);
+        self.vec_as_slice(_vec, ty)
+    }
+}
@@ -1,108 +1,432 @@
 use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
 
 use super::super::symex::*;
 use crate::expr::expr::*;
-use crate::expr::guard::Guard;
 use crate::expr::ty::*;
-use crate::symbol::nstring::NString;
-use crate::symbol::symbol::Symbol;
+use crate::program::program::bigint_to_usize;
+use crate::symbol::nstring::*;
+use crate::symbol::symbol::Ident;
 use crate::symex::place_state::PlaceState;
 use crate::symex::projection::Mode;
+use crate::symex::unsupported::unsupported;
 
-/// This mod defines symbolic execution of api in std::vec
-/// In our memory model, `vec` is a special pointer that owns
-/// an infinite array.
-
+/// This mod defines symbolic execution of api in std::vec.
+/// In our memory model, `vec` is a special pointer that owns an infinite
+/// array as its buffer. The capacity only bounds the length, so growing a
+/// `vec` does not move its elements. Whenever it is dropped, the buffer is
+/// dealloced. Elements holding pointers are not supported, since they must
+/// be dropped with the `vec`. An upper bound of the length is kept for each
+/// buffer, such that elements can be moved when the length is symbolic.
 impl<'cfg> Symex<'cfg> {
-    pub fn symex_vec_api(&mut self, fndef: &FunctionDef, args: Vec<Expr>, dest: Expr) {
-        let name = NString::from(fndef.0.trimmed_name());
+    pub fn symex_vec_api(&mut self, instance: Instance, args: Vec<Expr>, dest: Expr) {
+        let fty = Type::from(instance.ty());
+        let name = NString::from(fty.fn_def().0.trimmed_name());
         if name == "Vec::<T>::new" {
-            self.symex_vec_new(dest);
+            self.symex_vec_new(dest, self.ctx.constant_usize(0));
+        } else if name == "Vec::<T>::with_capacity" {
+            self.symex_vec_new(dest, args[0].clone());
         } else if name == "Vec::<T, A>::push" {
             self.symex_vec_push(args);
         } else if name == "Vec::<T, A>::pop" {
             self.symex_vec_pop(dest, args);
+        } else if name == "Vec::<T, A>::len" {
+            let _vec = self.deref_vec(args[0].clone());
+            self.assign(dest, self.ctx.vec_len(_vec), self.ctx._true().into());
+        } else if name == "Vec::<T, A>::capacity" {
+            let _vec = self.deref_vec(args[0].clone());
+            self.assign(dest, self.ctx.vec_cap(_vec), self.ctx._true().into());
+        } else if name == "Vec::<T, A>::insert" {
+            self.symex_vec_insert(args);
+        } else if name == "Vec::<T, A>::remove" {
+            self.symex_vec_remove(dest, args);
+        } else if name == "Vec::<T, A>::truncate" {
+            self.symex_vec_truncate(args);
+        } else if name == "Vec::<T, A>::clear" {
+            let _vec = self.deref_vec(args[0].clone());
+            let cap = self.ctx.vec_cap(_vec.clone());
+            self.update_vec(_vec, self.ctx.constant_usize(0), cap);
+        } else if name == "Vec::<T, A>::reserve" {
+            self.symex_vec_reserve(args);
+        } else if name == "Vec::<T, A>::extend_from_slice" {
+            self.symex_vec_extend_from_slice(args);
+        } else if name == "Vec::<T, A>::as_slice"
+            || name == "Vec::<T, A>::as_mut_slice"
+            || name.contains("Deref".into())
+        {
+            self.symex_vec_as_slice(dest, args);
+        } else if name.contains("Index".into()) {
+            self.symex_vec_index(instance, dest, args);
         } else {
-            panic!("Not support for {name:?}");
+            self.unsupported_function(name, args, dest);
         }
     }
 
-    fn symex_vec_new(&mut self, dest: Expr) {
-        let lhs = dest.clone();
-        let object = self.exec_state.new_object(lhs.ty().pointee_ty());
-
-        // Construct vec pointer
-        let inner_pt = self.ctx.address_of(object.clone(), object.extract_address_type());
-        let len = self.ctx.constant_usize(0);
-        let ident = object.extract_inner_expr().extract_symbol().ident();
-        let cap_sym = Symbol::from(ident + "_size");
-        let cap = self.ctx.mk_symbol(cap_sym, Type::usize_type());
-        let _vec = self.ctx._vec(inner_pt, len, cap, dest.ty());
-        self.assign(lhs, _vec, self.ctx._true().into());
+    /// A new empty `vec` owning a fresh buffer
+    pub(crate) fn new_vec(&mut self, ty: Type, cap: Expr) -> Expr {
+        let buffer = self.new_vec_buffer(ty, 0);
+        let address = self.ctx.address_of(buffer.clone(), buffer.extract_address_type());
+        self.ctx._vec(address, self.ctx.constant_usize(0), cap, ty)
+    }
+
+    /// A fresh buffer for a new `vec` of type `ty`, whose length is at most
+    /// `max_len`
+    pub(crate) fn new_vec_buffer(&mut self, ty: Type, max_len: usize) -> Expr {
+        let elem_ty = ty.vec_elem_type();
+        if elem_ty.is_zero_sized_type() || elem_ty.contains_ptr_field() {
+            unsupported!("Vec of {elem_ty:?}");
+        }
+        let object = self.exec_state.new_object(Type::infinite_array_type(elem_ty));
 
         // Track new object
         self.track_new_object(object.clone());
 
-        // The newly object is owned by the box pointer
+        // The newly buffer is owned by the vec
         let place_state = PlaceState::Own;
-        self.exec_state.update_place_state(object, place_state);
+        self.exec_state.update_place_state(object.clone(), place_state);
+
+        self.vec_max_lens.insert(Self::buffer_ident(&object).unwrap(), max_len);
+        object
+    }
+
+    /// The buffer of the vec is dealloced
+    pub(crate) fn symex_vec_drop(&mut self, _vec: Expr) {
+        let pt = self.ctx.vec_raw(_vec);
+        let ty = pt.ty().pointee_ty();
+        self.dealloc_pointer(pt, Mode::Drop, ty);
+    }
+
+    fn symex_vec_new(&mut self, dest: Expr, cap: Expr) {
+        let lhs = dest.clone();
+        let _vec = self.new_vec(lhs.ty(), cap);
+        self.assign(lhs, _vec, self.ctx._true().into());
     }
 
     fn symex_vec_push(&mut self, args: Vec<Expr>) {
-        let guard = Guard::from(self.ctx._true());
-        let _vec =
-            self.make_deref(args[0].clone(), Mode::Read, guard.clone(), args[0].ty().pointee_ty());
-        let value = args[1].clone();
-
-        let inner_pt = self.ctx.inner_pointer(_vec.clone());
-        let old_len = self.ctx.vec_len(_vec.clone());
-        let len = self.ctx.add(old_len.clone(), self.ctx.constant_usize(1));
-        let cap = self.ctx.vec_cap(_vec.clone());
+        let _vec = self.deref_vec(args[0].clone());
+        let buffer = self.vec_buffer(_vec.clone());
+        let len = self.ctx.vec_len(_vec.clone());
+        let cap = self.grow_vec(_vec.clone(), self.ctx.constant_usize(1));
+        self.grow_vec_max_len(buffer.clone(), 1);
 
-        // Update the inner array
-        let inner_array =
-            self.make_deref(inner_pt.clone(), Mode::Read, guard.clone(), _vec.ty().pointee_ty());
-        let array = self.ctx.object(inner_array);
-        let elem_ty = array.ty().elem_type();
-        let index = self.ctx.index(array, old_len, elem_ty);
-        self.assign(index, value, guard.clone());
-        // TODO: handle cap
+        // Write the new element
+        let elem = self.ctx.index(buffer, len.clone(), _vec.ty().vec_elem_type());
+        self.assign(elem, args[1].clone(), self.ctx._true().into());
 
-        let lhs = _vec;
-        let rhs = self.ctx._vec(inner_pt, len, cap, lhs.ty());
-        self.assign(lhs, rhs, guard);
+        let new_len = self.ctx.add(len, self.ctx.constant_usize(1));
+        self.update_vec(_vec, new_len, cap);
     }
 
     fn symex_vec_pop(&mut self, dest: Expr, args: Vec<Expr>) {
-        todo!();
-        // let guard = Guard::from(self.ctx._true());
-        // let _vec =
-        //     self.make_deref(args[0].clone(), Mode::Read, guard.clone(), args[0].ty().pointee_ty());
-        // let inner_pt = self.ctx.inner_pointer(_vec.clone());
-        // let inner_array =
-        //     self.make_deref(inner_pt.clone(), Mode::Read, guard.clone(), _vec.ty().pointee_ty());
-        // let array = self.ctx.object(inner_array);
-        // let old_len = self.ctx.vec_len(_vec.clone());
-        // let zero = self.ctx.constant_usize(0);
-        // let cond = self.ctx.eq(old_len.clone(), zero.clone());
-        // let sub_one = self.ctx.sub(old_len, self.ctx.constant_usize(1));
-        // let mut len = self.ctx.ite(cond.clone(), zero, sub_one.clone());
-        // len.simplify();
-        // let cap = self.ctx.vec_cap(_vec.clone());
-
-        // // Return pop value
-        // let lhs = dest;
-        // let none = self.ctx.constant(vec![], ty)
-        // self.ctx.variant(self.ctx.constant_usize(0), None, lhs.ty());
-        // let i = sub_one;
-        // let data = self.ctx.index(array.clone(), i, array.ty().elem_type());
-        // let some = self.ctx.variant(self.ctx.constant_usize(1), data, lhs.ty());
-        // let rhs = self.ctx.ite(cond, none, some);
-        // self.assign(lhs, rhs, guard.clone());
-
-        // // Update vec
-        // let lhs = _vec;
-        // let rhs = self.ctx._vec(inner_pt, len, cap, lhs.ty());
-        // self.assign(lhs, rhs, guard);
+        let _vec = self.deref_vec(args[0].clone());
+        let buffer = self.vec_buffer(_vec.clone());
+        let len = self.ctx.vec_len(_vec.clone());
+        let cap = self.ctx.vec_cap(_vec.clone());
+        let is_empty = self.ctx.eq(len.clone(), self.ctx.constant_usize(0));
+        let last = self.ctx.sub(len.clone(), self.ctx.constant_usize(1));
+        // Read an empty vec at `0`, since `len - 1` underflows on a constant `0`
+        let index = self.ctx.ite(is_empty.clone(), self.ctx.constant_usize(0), last.clone());
+
+        // Return `None` for an empty vec, otherwise the last element
+        let ty = dest.ty();
+        let none = self.ctx.constant_adt(vec![self.ctx.constant_usize(0).extract_constant()], ty);
+        let elem = self.ctx.index(buffer, index, _vec.ty().vec_elem_type());
+        let data = self.ctx.aggregate(vec![elem], ty.enum_variant_data_type(1));
+        let some = self.ctx.variant(self.ctx.constant_usize(1), data, ty);
+        let rhs = self.ctx.ite(is_empty.clone(), none, some);
+        self.assign(dest, rhs, self.ctx._true().into());
+
+        let new_len = self.ctx.ite(is_empty, len, last);
+        self.update_vec(_vec, new_len, cap);
+    }
+
+    fn symex_vec_insert(&mut self, args: Vec<Expr>) {
+        let _vec = self.deref_vec(args[0].clone());
+        let buffer = self.vec_buffer(_vec.clone());
+        let len = self.ctx.vec_len(_vec.clone());
+        let i = args[1].clone();
+        let msg = NString::from("vec failure: insertion index out of bounds");
+        self.claim(msg, self.ctx.gt(i.clone(), len.clone()));
+        let n = self.vec_len_bound(_vec.clone(), buffer.clone(), "Vec::insert");
+        let cap = self.grow_vec(_vec.clone(), self.ctx.constant_usize(1));
+        self.grow_vec_max_len(buffer.clone(), 1);
+
+        // Shift the elements after `i` to the right. The old elements are read
+        // before being overwritten.
+        let elem_ty = _vec.ty().vec_elem_type();
+        for k in (0..n).rev() {
+            let j = self.ctx.constant_usize(k);
+            let next = self.ctx.constant_usize(k + 1);
+            let cond = self.ctx.ge(j.clone(), i.clone());
+            let rhs = self.ctx.ite(
+                cond,
+                self.ctx.index(buffer.clone(), j, elem_ty),
+                self.ctx.index(buffer.clone(), next.clone(), elem_ty),
+            );
+            let lhs = self.ctx.index(buffer.clone(), next, elem_ty);
+            self.assign(lhs, rhs, self.ctx._true().into());
+        }
+        let elem = self.ctx.index(buffer, i, elem_ty);
+        self.assign(elem, args[2].clone(), self.ctx._true().into());
+
+        let new_len = self.ctx.add(len, self.ctx.constant_usize(1));
+        self.update_vec(_vec, new_len, cap);
+    }
+
+    fn symex_vec_remove(&mut self, dest: Expr, args: Vec<Expr>) {
+        let _vec = self.deref_vec(args[0].clone());
+        let buffer = self.vec_buffer(_vec.clone());
+        let len = self.ctx.vec_len(_vec.clone());
+        let cap = self.ctx.vec_cap(_vec.clone());
+        let i = args[1].clone();
+        let msg = NString::from("vec failure: removal index out of bounds");
+        self.claim(msg, self.ctx.ge(i.clone(), len.clone()));
+
+        // Return the removed element
+        let elem_ty = _vec.ty().vec_elem_type();
+        let elem = self.ctx.index(buffer.clone(), i.clone(), elem_ty);
+        self.assign(dest, elem, self.ctx._true().into());
+
+        // Shift the elements after `i` to the left
+        let n = self.vec_len_bound(_vec.clone(), buffer.clone(), "Vec::remove");
+        for k in 1..n {
+            let prev = self.ctx.constant_usize(k - 1);
+            let j = self.ctx.constant_usize(k);
+            let cond = self.ctx.gt(j.clone(), i.clone());
+            let rhs = self.ctx.ite(
+                cond,
+                self.ctx.index(buffer.clone(), j, elem_ty),
+                self.ctx.index(buffer.clone(), prev.clone(), elem_ty),
+            );
+            let lhs = self.ctx.index(buffer.clone(), prev, elem_ty);
+            self.assign(lhs, rhs, self.ctx._true().into());
+        }
+
+        let new_len = self.ctx.sub(len, self.ctx.constant_usize(1));
+        self.update_vec(_vec, new_len, cap);
+    }
+
+    fn symex_vec_truncate(&mut self, args: Vec<Expr>) {
+        let _vec = self.deref_vec(args[0].clone());
+        let len = self.ctx.vec_len(_vec.clone());
+        let cap = self.ctx.vec_cap(_vec.clone());
+        let n = args[1].clone();
+        let new_len = self.ctx.ite(self.ctx.lt(n.clone(), len.clone()), n, len);
+        self.update_vec(_vec, new_len, cap);
+    }
+
+    fn symex_vec_reserve(&mut self, args: Vec<Expr>) {
+        let _vec = self.deref_vec(args[0].clone());
+        let len = self.ctx.vec_len(_vec.clone());
+        let cap = self.grow_vec(_vec.clone(), args[1].clone());
+        self.update_vec(_vec, len, cap);
+    }
+
+    fn symex_vec_extend_from_slice(&mut self, args: Vec<Expr>) {
+        let _vec = self.deref_vec(args[0].clone());
+        self.extend_vec(_vec, args[1].clone());
+    }
+
+    /// Append the elements of the slice `pt` points to
+    pub(crate) fn extend_vec(&mut self, _vec: Expr, pt: Expr) {
+        let buffer = self.vec_buffer(_vec.clone());
+        let len = self.ctx.vec_len(_vec.clone());
+        let n = self.ctx.pointer_meta(pt.clone());
+        let cap = self.grow_vec(_vec.clone(), n.clone());
+
+        // Copy the elements of the slice. The elements beyond its length keep
+        // the old values of the buffer.
+        let slice =
+            self.make_deref(pt.clone(), Mode::Read, self.ctx._true().into(), pt.ty().pointee_ty());
+        let elem_ty = _vec.ty().vec_elem_type();
+        let bound = self.slice_len_bound(n.clone(), slice.clone());
+        self.grow_vec_max_len(buffer.clone(), bound);
+        for k in 0..bound {
+            let j = self.ctx.constant_usize(k);
+            let lhs = self.ctx.index(buffer.clone(), self.ctx.add(len.clone(), j.clone()), elem_ty);
+            let rhs = self.ctx.ite(
+                self.ctx.lt(j.clone(), n.clone()),
+                self.ctx.index(slice.clone(), j, elem_ty),
+                lhs.clone(),
+            );
+            self.assign(lhs, rhs, self.ctx._true().into());
+        }
+
+        let new_len = self.ctx.add(len, n);
+        self.update_vec(_vec, new_len, cap);
+    }
+
+    /// `as_slice`, `as_mut_slice`, `deref` and `deref_mut` return the
+    /// initialized part of the buffer.
+    fn symex_vec_as_slice(&mut self, dest: Expr, args: Vec<Expr>) {
+        let _vec = self.deref_vec(args[0].clone());
+        let slice = self.vec_as_slice(_vec, dest.ty());
+        self.assign(dest, slice, self.ctx._true().into());
+    }
+
+    /// The slice of type `ty` over the initialized part of the buffer
+    pub(crate) fn vec_as_slice(&self, _vec: Expr, ty: Type) -> Expr {
+        let len = self.ctx.vec_len(_vec.clone());
+        self.ctx.pointer(self.ctx.vec_raw(_vec), Some(len), ty)
+    }
+
+    /// `index` and `index_mut` with an `usize` index
+    fn symex_vec_index(&mut self, instance: Instance, dest: Expr, args: Vec<Expr>) {
+        if !args[1].ty().is_usize() {
+            unsupported!("{:?} with {:?}", instance.name(), args[1].ty());
+        }
+        let _vec = self.deref_vec(args[0].clone());
+        let buffer = self.vec_buffer(_vec.clone());
+        let len = self.ctx.vec_len(_vec.clone());
+        let i = args[1].clone();
+        let msg = NString::from("vec failure: index out of bounds");
+        self.claim(msg, self.ctx.ge(i.clone(), len));
+
+        let address = self.borrow_elem(buffer, i, dest.ty());
+        self.assign(dest, address, self.ctx._true().into());
+    }
+
+    /// The `vec` that `pt` points to
+    fn deref_vec(&mut self, pt: Expr) -> Expr {
+        let ty = pt.ty().pointee_ty();
+        self.make_deref(pt, Mode::Read, self.ctx._true().into(), ty)
+    }
+
+    /// The buffer that the `vec` owns
+    pub(crate) fn vec_buffer(&mut self, _vec: Expr) -> Expr {
+        let pt = self.ctx.vec_raw(_vec);
+        let ty = pt.ty().pointee_ty();
+        self.make_deref(pt, Mode::Read, self.ctx._true().into(), ty)
+    }
+
+    /// Assign the new length and capacity to the `vec`. The `vec` wrapped by a
+    /// `String` is assigned with the `String`, since constants are not
+    /// propagated through the update of a field.
+    pub(crate) fn update_vec(&mut self, _vec: Expr, len: Expr, cap: Expr) {
+        let rhs = self.ctx._vec(self.ctx.vec_raw(_vec.clone()), len, cap, _vec.ty());
+        if _vec.is_index() && _vec.extract_object().ty().is_string() {
+            let string = _vec.extract_object();
+            let rhs = self.ctx.aggregate(vec![rhs], string.ty());
+            self.assign(string, rhs, self.ctx._true().into());
+        } else {
+            self.assign(_vec, rhs, self.ctx._true().into());
+        }
+    }
+
+    /// The capacity after reserving `additional` elements. The growth is
+    /// amortized as `RawVec`, i.e., the capacity is doubled at least.
+    fn grow_vec(&mut self, _vec: Expr, additional: Expr) -> Expr {
+        let len = self.ctx.vec_len(_vec.clone());
+        let cap = self.ctx.vec_cap(_vec.clone());
+        let required = self.ctx.add(len, additional);
+
+        // The size of the buffer can not exceed `isize::MAX`
+        let elem_size = _vec.ty().vec_elem_type().size();
+        let max_cap = self.ctx.constant_usize(isize::MAX as usize / elem_size);
+        let msg = NString::from("vec failure: capacity overflow");
+        self.claim(msg, self.ctx.gt(required.clone(), max_cap));
+
+        let double = self.ctx.add(cap.clone(), cap.clone());
+        let min_cap = match elem_size {
+            1 => 8,
+            2..=1024 => 4,
+            _ => 1,
+        };
+        let min_cap = self.ctx.constant_usize(min_cap);
+        let new_cap =
+            self.ctx.ite(self.ctx.gt(double.clone(), required.clone()), double, required.clone());
+        let new_cap = self.ctx.ite(self.ctx.gt(min_cap.clone(), new_cap.clone()), min_cap, new_cap);
+        self.ctx.ite(self.ctx.gt(required, cap.clone()), new_cap, cap)
+    }
+
+    /// Moving elements needs a constant bound of the length of the `vec`,
+    /// i.e., its length or the upper bound kept for its buffer. Moving the
+    /// elements beyond the length is harmless, since they are not initialized.
+    fn vec_len_bound(&mut self, _vec: Expr, buffer: Expr, function: &str) -> usize {
+        let len = self.ctx.vec_len(_vec);
+        match self.constant_usize(len).or_else(|| self.vec_max_len(&buffer)) {
+            Some(n) => n,
+            None => unsupported!("{function} with non-constant length"),
+        }
+    }
+
+    /// The upper bound of the length of the `vec` owning `buffer`
+    fn vec_max_len(&self, buffer: &Expr) -> Option<usize> {
+        self.vec_max_lens.get(&Self::buffer_ident(buffer)?).copied()
+    }
+
+    /// The length of the `vec` owning `buffer` grows by at most `n`. If the
+    /// buffer is not known during symex, the bounds of all buffers are lost.
+    fn grow_vec_max_len(&mut self, buffer: Expr, n: usize) {
+        let Some(ident) = Self::buffer_ident(&buffer) else {
+            self.vec_max_lens.clear();
+            return;
+        };
+        if let Some(max_len) = self.vec_max_lens.get_mut(&ident) {
+            *max_len += n;
+        }
+    }
+
+    /// The heap object of `buffer` if it is known during symex
+    fn buffer_ident(buffer: &Expr) -> Option<Ident> {
+        if buffer.is_object() {
+            Some(buffer.extract_inner_expr().extract_symbol().ident())
+        } else if buffer.is_symbol() {
+            Some(buffer.extract_symbol().ident())
+        } else {
+            None
+        }
+    }
+
+    /// A constant bound of the length `len` of `slice`, i.e., its length or
+    /// the length of the array being sliced
+    fn slice_len_bound(&mut self, len: Expr, slice: Expr) -> usize {
+        if let Some(n) = self.constant_usize(len) {
+            return n;
+        }
+        if slice.is_slice() && slice.extract_object().ty().is_array() {
+            let n = slice.extract_object().ty().array_len();
+            let start = self.constant_usize(slice.extract_slice_start());
+            // A slice starting after the end of the array is empty
+            if let (Some(n), Some(start)) = (n, start) {
+                return n.saturating_sub(start);
+            }
+        }
+        unsupported!("slice {slice:?} with non-constant length")
+    }
+
+    /// The value of `e` if it is a constant during symex
+    fn constant_usize(&mut self, mut e: Expr) -> Option<usize> {
+        self.replace_predicates(&mut e);
+        self.rename(&mut e);
+        e.simplify();
+        e.is_constant().then(|| bigint_to_usize(&e.extract_constant().to_integer()))
+    }
+
+    /// Borrow the element at index `i` of `array` by a pointer of type
+    /// `pointer_ty`. Pointers in our memory model have constant offsets. Thus,
+    /// a shared borrow at a non-constant index points to a copy of the
+    /// element, and a mutable one is not supported.
+    pub(crate) fn borrow_elem(&mut self, array: Expr, i: Expr, pointer_ty: Type) -> Expr {
+        let elem_ty = pointer_ty.pointee_ty();
+        if self.constant_usize(i.clone()).is_some() || pointer_ty.is_mut_ptr() {
+            let elem = self.elem_of_constant_index(array, i, elem_ty);
+            return self.ctx.address_of(elem, pointer_ty);
+        }
+        let elem = self.ctx.index(array, i, elem_ty);
+        let copy = self.arbitrary_object(elem);
+        self.ctx.address_of(copy, pointer_ty)
+    }
+
+    /// Pointers in our memory model have constant offsets. Thus, the element
+    /// being borrowed must be at a constant index.
+    pub(crate) fn elem_of_constant_index(&mut self, array: Expr, mut i: Expr, ty: Type) -> Expr {
+        self.replace_predicates(&mut i);
+        self.rename(&mut i);
+        i.simplify();
+        if !i.is_constant() {
+            unsupported!("non-constant index {i:?}");
+        }
+        self.ctx.index(array, i, ty)
     }
 }
@@ -4,11 +4,14 @@ use stable_mir::abi::FieldsShape;
 use stable_mir::mir::Operand;
 use stable_mir::mir::Place;
 use stable_mir::mir::alloc::GlobalAlloc;
+use stable_mir::target::MachineInfo;
 use stable_mir::ty::*;
 
+use super::place_state::PlaceState;
 use super::projection::*;
 use super::state::State;
 use super::symex::*;
+use super::unsupported::unsupported;
 use crate::expr::constant::*;
 use crate::expr::expr::*;
 use crate::expr::guard::*;
@@ -17,6 +20,7 @@ use crate::program::function::*;
 use crate::program::program::*;
 use crate::symbol::nstring::*;
 use crate::symbol::symbol::*;
+use crate::vc::vc::CoverKind;
 
 impl<'cfg> Symex<'cfg> {
     pub(super) fn merge_states(&mut self, pc: Pc) -> bool {
@@ -92,15 +96,24 @@ impl<'cfg> Symex<'cfg> {
         if self.top().function.is_loop_entry(pc) {
             if self.top().loop_stack.is_empty() || self.top().loop_stack.last().unwrap().0 != pc {
                 // New loop
+                if self.induction.is_some() && !self.has_loop_invariant(pc) {
+                    self.havoc_loop(pc);
+                }
                 self.top_mut().loop_stack.push((pc, 1));
             } else {
                 self.top_mut().loop_stack.last_mut().unwrap().1 += 1;
+                if self.top().loop_stack.last().unwrap().1 == 2 && self.has_loop_invariant(pc) {
+                    // The second iteration starts from an arbitrary state
+                    self.havoc_writes(pc, "loop invariant");
+                }
+            }
+            if self.config.is_text_output() {
+                println!(
+                    "Unwinding loop bb{pc} in {:?} for {} times",
+                    self.top().function.name(),
+                    self.top().loop_stack.last().unwrap().1
+                );
             }
-            println!(
-                "Unwinding loop bb{pc} in {:?} for {} times",
-                self.top().function.name(),
-                self.top().loop_stack.last().unwrap().1
-            );
         }
     }
 
@@ -142,11 +155,15 @@ impl<'cfg> Symex<'cfg> {
                 } else {
                     assert!(allocation.provenance.ptrs.len() == 1);
                     let (_, prov) = &allocation.provenance.ptrs[0];
-                    self.make_global_alloc(prov, ty)
+                    self.make_global_alloc(prov, allocation, ty)
                 }
             }
             ConstantKind::ZeroSized => self.ctx.constant_zst(ty),
-            _ => panic!("Not support {:?}", mirconst.kind()),
+            ConstantKind::Unevaluated(c) if c.promoted.is_some() => match eval_promoted(mirconst) {
+                Some(value) => self.make_mirconst(&value),
+                None => unsupported!("constant {:?}", mirconst.kind()),
+            },
+            _ => unsupported!("constant {:?}", mirconst.kind()),
         }
     }
 
@@ -158,10 +175,10 @@ impl<'cfg> Symex<'cfg> {
                     let n = tyconst.eval_target_usize().expect("Not usize") as usize;
                     self.ctx.constant_integer(BigInt::from(n), ty)
                 } else {
-                    todo!()
+                    unsupported!("type constant {tyconst:?}")
                 }
             }
-            _ => todo!("{:?}", tyconst.kind()),
+            _ => unsupported!("type constant {:?}", tyconst.kind()),
         }
     }
 
@@ -188,7 +205,11 @@ impl<'cfg> Symex<'cfg> {
                 .map(|byte| byte.unwrap())
                 .collect::<Vec<_>>();
             assert!(bytes.len() == ty.size());
-            Constant::Integer(read_target_integer(&bytes))
+            if ty.is_unsigned() {
+                Constant::Integer(read_target_uint(&bytes))
+            } else {
+                Constant::Integer(read_target_integer(&bytes))
+            }
         } else if ty.is_struct() || ty.is_tuple() {
             let shape = ty.shape();
             let n = shape.fields.count();
@@ -235,11 +256,12 @@ impl<'cfg> Symex<'cfg> {
                 Constant::Adt(vec![idx, value], ty)
             }
         } else {
-            todo!("Allocation {ty:?}")
+            unsupported!("allocation of {ty:?}")
         }
     }
 
-    fn make_global_alloc(&mut self, prov: &Prov, ty: Type) -> Expr {
+    /// `pointer` is the allocation of the constant pointer with provenance `prov`
+    fn make_global_alloc(&mut self, prov: &Prov, pointer: &Allocation, ty: Type) -> Expr {
         let global_alloc = GlobalAlloc::from(prov.0);
         match global_alloc {
             GlobalAlloc::Static(def) => {
@@ -249,10 +271,78 @@ impl<'cfg> Symex<'cfg> {
                 let object = self.exec_state.ns.lookup_object(ident);
                 self.ctx.address_of(object.clone(), ty)
             }
-            _ => panic!("Do not support global alloc {global_alloc:?}"),
+            GlobalAlloc::Memory(memory) => self.make_memory_alloc(&memory, pointer, ty),
+            _ => unsupported!("global allocation {global_alloc:?}"),
         }
     }
 
+    /// A pointer to constant memory, e.g., a string literal. The memory is
+    /// copied to a fresh object, which is never dealloced. For a slice, the
+    /// length follows the address in `pointer`.
+    fn make_memory_alloc(&mut self, memory: &Allocation, pointer: &Allocation, ty: Type) -> Expr {
+        let width = MachineInfo::target_pointer_width().bytes();
+        if !ty.is_primitive_ptr() || pointer.read_partial_uint(0..width) != Ok(0) {
+            unsupported!("constant memory allocation of {ty:?}");
+        }
+
+        let (object_ty, meta) = if ty.is_slice_ptr() {
+            let len = pointer.read_partial_uint(width..2 * width).expect("No length?") as usize;
+            let array_ty = Type::array_type(ty.pointee_ty().elem_type(), len as u64);
+            (array_ty, Some(self.ctx.constant_usize(len)))
+        } else {
+            (ty.pointee_ty(), None)
+        };
+        let object = self.exec_state.new_object(object_ty);
+        let value = if let Some((_, prov)) = memory.provenance.ptrs.first() {
+            // The memory holds a pointer, e.g., a promoted `&&str`
+            if memory.provenance.ptrs.len() > 1 || !object_ty.is_primitive_ptr() {
+                unsupported!("constant memory allocation of {ty:?}");
+            }
+            Some(self.make_global_alloc(prov, memory, object_ty))
+        } else if object_ty.is_array() {
+            // Constant arrays in our memory model hold one value. Thus, the
+            // elements are aggregated.
+            let elem_ty = object_ty.elem_type();
+            let size = elem_ty.size();
+            let mut elems = Vec::new();
+            for i in 0..object_ty.array_len().unwrap_or(0) {
+                let elem =
+                    self.make_allocation_rec(&memory.bytes[i * size..(i + 1) * size], elem_ty);
+                elems.push(self.ctx.constant(elem, elem_ty));
+            }
+            // An empty literal has no elements
+            (!elems.is_empty()).then(|| self.ctx.aggregate(elems, object_ty))
+        } else {
+            let value = self.make_allocation_rec(&memory.bytes, object_ty);
+            Some(self.ctx.constant(value, object_ty))
+        };
+        if let Some(value) = value {
+            self.assign(object.clone(), value, self.ctx._true().into());
+        }
+        self.track_new_object(object.clone());
+        self.exec_state.update_place_state(object.clone(), PlaceState::Own);
+
+        // The operands are `l1` exprs
+        let mut object = object;
+        self.exec_state.rename(&mut object, Level::Level1);
+        let address = self.ctx.address_of(object.clone(), object.extract_address_type());
+        if meta.is_some() {
+            self.ctx.pointer(address, meta, ty)
+        } else {
+            self.ctx.address_of(object, ty)
+        }
+    }
+
+    /// The value of a string literal, e.g., the message of `rbmc::assert`
+    pub(super) fn make_str_literal(&self, operand: &Operand) -> Option<String> {
+        let Operand::Constant(constant) = operand else { return None };
+        let ConstantKind::Allocated(allocation) = constant.const_.kind() else { return None };
+        let (_, prov) = allocation.provenance.ptrs.first()?;
+        let GlobalAlloc::Memory(memory) = GlobalAlloc::from(prov.0) else { return None };
+        let bytes = memory.bytes.iter().map(|byte| byte.unwrap_or(0)).collect::<Vec<_>>();
+        String::from_utf8(bytes).ok()
+    }
+
     /// Return `l1` expr
     pub(super) fn make_operand(&mut self, operand: &Operand) -> Expr {
         match operand {
@@ -309,6 +399,15 @@ impl<'cfg> Symex<'cfg> {
         self.vc_system.borrow_mut().assume(cond);
     }
 
+    /// Assume `cond` on the current path, i.e., `path -> cond`
+    pub(super) fn assume_on_path(&mut self, mut cond: Expr) {
+        self.replace_predicates(&mut cond);
+        let guard = self.exec_state.cur_state.guard.to_expr();
+        let mut cond = self.ctx.or(self.ctx.not(guard), cond);
+        self.rename(&mut cond);
+        self.assume(cond);
+    }
+
     /// Generating assertion in form: `path /\ error`,
     pub(super) fn claim(&mut self, msg: NString, mut error: Expr) {
         self.replace_predicates(&mut error);
@@ -325,6 +424,26 @@ impl<'cfg> Symex<'cfg> {
         if cond.is_false() {
             return;
         }
+        if self.is_induction_hypothesis() {
+            self.assume(self.ctx.not(cond));
+            return;
+        }
         self.vc_system.borrow_mut().assert(msg, cond, self.exec_state.cur_span());
     }
+
+    /// Generating cover in form: `path /\ cond`. It is recorded even if it is
+    /// false, so that it is reported as unsatisfiable.
+    pub(super) fn cover(&mut self, kind: CoverKind, msg: NString, mut cond: Expr) {
+        // The inductive step starts from an arbitrary state
+        if self.induction.is_some() {
+            return;
+        }
+        self.replace_predicates(&mut cond);
+        self.rename(&mut cond);
+        let mut guard = self.exec_state.cur_state.guard.clone();
+        guard.add(cond);
+        let mut cond = guard.to_expr();
+        cond.simplify();
+        self.vc_system.borrow_mut().cover(kind, msg, cond, self.exec_state.cur_span());
+    }
 }
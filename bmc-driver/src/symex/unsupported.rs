@@ -0,0 +1,40 @@
+use stable_mir::ty::Span;
+
+use crate::symbol::nstring::NString;
+
+/// A construct that symex can not handle. The path reaching it is abandoned,
+/// and the verdict of the harness is `unknown` if symex reaches any
+/// unsupported construct.
+#[derive(Clone, PartialEq)]
+pub struct Unsupported {
+    pub construct: String,
+    /// The span of the latest statement in the local crate
+    pub span: Option<Span>,
+    pub function: Option<NString>,
+}
+
+/// The payload of the panic raised by `unsupported!`
+pub(super) struct UnsupportedConstruct(pub(super) String);
+
+/// Stop the statement or the terminator at an unsupported construct. It is
+/// returned as an error by the handler, which fills in the span and the
+/// function.
+macro_rules! unsupported {
+    ($($arg:tt)*) => {
+        std::panic::panic_any(
+            $crate::symex::unsupported::UnsupportedConstruct(format!($($arg)*))
+        )
+    };
+}
+pub(super) use unsupported;
+
+/// Unsupported constructs are reported by `Bmc`. Keep the panic message
+/// and backtrace for the other panics.
+pub fn install_panic_hook() {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if !info.payload().is::<UnsupportedConstruct>() {
+            hook(info)
+        }
+    }));
+}
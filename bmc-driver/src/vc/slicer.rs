@@ -64,6 +64,8 @@ impl Slicer {
                 vc.is_sliced = false;
                 self.get_symbols(cond, true);
             }
+            // Covers are checked without slicing
+            StepKind::Cover(..) => vc.is_sliced = true,
         };
     }
 }
@@ -10,11 +10,21 @@ use crate::expr::context::ExprCtx;
 use crate::expr::expr::*;
 use crate::symbol::nstring::NString;
 
+/// Conditions checked to be satisfiable instead of violated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverKind {
+    /// `rbmc::cover`
+    Cover,
+    /// The path of `rbmc::assert` with `--assertion-reach-checks`
+    Reach,
+}
+
 #[derive(Clone)]
 pub enum StepKind {
     Assign(Expr, Expr),
     Assert(NString, Expr),
     Assume(Expr),
+    Cover(CoverKind, NString, Expr),
 }
 
 impl Debug for StepKind {
@@ -23,6 +33,7 @@ impl Debug for StepKind {
             StepKind::Assign(lhs, rhs) => write!(f, "{lhs:?} = {rhs:?}"),
             StepKind::Assert(msg, cond) => write!(f, "{msg:?}\n    ASSERT: {cond:?}"),
             StepKind::Assume(cond) => write!(f, "{cond:?}"),
+            StepKind::Cover(kind, msg, cond) => write!(f, "{kind:?} {msg:?}\n    COVER: {cond:?}"),
         }
     }
 }
@@ -51,17 +62,21 @@ impl SSAStep {
         matches!(self.kind, StepKind::Assume(..))
     }
 
+    pub fn is_cover(&self) -> bool {
+        matches!(self.kind, StepKind::Cover(..))
+    }
+
     pub fn msg(&self) -> NString {
-        if let StepKind::Assert(msg, _) = &self.kind {
-            return *msg;
+        match &self.kind {
+            StepKind::Assert(msg, _) | StepKind::Cover(_, msg, _) => *msg,
+            _ => panic!("Not assertion or cover"),
         }
-        panic!("Not assertion")
     }
 
     pub fn cond(&self) -> Expr {
         match &self.kind {
-            StepKind::Assert(_, c) | StepKind::Assume(c) => c.clone(),
-            _ => panic!("Not assert or assume"),
+            StepKind::Assert(_, c) | StepKind::Assume(c) | StepKind::Cover(_, _, c) => c.clone(),
+            _ => panic!("Not assert, assume or cover"),
         }
     }
 }
@@ -78,11 +93,13 @@ pub struct VCSystem {
     _ctx: ExprCtx,
     pub(super) ssa_steps: Vec<SSAStep>,
     pub(super) asserts_map: HashMap<usize, usize>,
+    /// The positions of covers in SSA steps
+    covers: Vec<usize>,
 }
 
 impl VCSystem {
     pub fn new(_ctx: ExprCtx) -> Self {
-        VCSystem { _ctx, ssa_steps: Vec::new(), asserts_map: HashMap::new() }
+        VCSystem { _ctx, ssa_steps: Vec::new(), asserts_map: HashMap::new(), covers: Vec::new() }
     }
 
     pub fn num_step(&self) -> usize {
@@ -93,6 +110,10 @@ impl VCSystem {
         self.asserts_map.len()
     }
 
+    pub fn num_covers(&self) -> usize {
+        self.covers.len()
+    }
+
     pub fn assign(&mut self, lhs: Expr, rhs: Expr, span: Option<Span>) {
         self.ssa_steps.push(SSAStep::new(StepKind::Assign(lhs, rhs), span));
     }
@@ -106,6 +127,13 @@ impl VCSystem {
         self.ssa_steps.push(SSAStep::new(StepKind::Assume(cond), None));
     }
 
+    /// Covers are not assertions. They are checked one by one after the
+    /// assertions.
+    pub fn cover(&mut self, kind: CoverKind, msg: NString, cond: Expr, span: Option<Span>) {
+        self.covers.push(self.ssa_steps.len());
+        self.ssa_steps.push(SSAStep::new(StepKind::Cover(kind, msg, cond), span));
+    }
+
     pub fn nth(&self, n: usize) -> SSAStep {
         assert!(n < self.ssa_steps.len());
         self.ssa_steps[n].clone()
@@ -116,10 +144,28 @@ impl VCSystem {
         self.nth(*self.asserts_map.get(&n).unwrap())
     }
 
+    /// The position of the `n`-th assertion in SSA steps
+    pub fn nth_assertion_position(&self, n: usize) -> usize {
+        assert!(n < self.asserts_map.len());
+        *self.asserts_map.get(&n).unwrap()
+    }
+
+    pub fn nth_cover(&self, n: usize) -> SSAStep {
+        assert!(n < self.covers.len());
+        self.nth(self.covers[n])
+    }
+
+    pub fn nth_cover_position(&self, n: usize) -> usize {
+        assert!(n < self.covers.len());
+        self.covers[n]
+    }
+
     pub fn set_nth_assertion(&mut self, n: usize) {
         let m = *self.asserts_map.get(&n).unwrap();
         for (i, vc) in self.ssa_steps.iter_mut().enumerate() {
-            if vc.is_assert() {
+            if vc.is_cover() {
+                vc.is_sliced = true;
+            } else if vc.is_assert() {
                 vc.is_sliced = i != m;
             } else {
                 vc.is_sliced = i > m;
@@ -165,7 +211,7 @@ impl VCSystem {
                 if self.ssa_steps[j].is_sliced {
                     continue;
                 }
-                if self.ssa_steps[j].is_assert() {
+                if self.ssa_steps[j].is_assert() || self.ssa_steps[j].is_cover() {
                     continue;
                 }
                 if self.ssa_steps[j].is_assume() {
@@ -0,0 +1,284 @@
+//! Attribute and derive macros of RBMC. They are re-exported by the `rbmc`
+//! library.
+//!
+//! The macros mark items with tool attributes of `rbmctool`, which is
+//! registered by `rbmc` and retrieved by `bmc-driver` through Stable MIR.
+//! Contracts also generate the functions to check and to replace calls.
+
+use proc_macro::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+
+/// Mark a function as a proof harness. Every harness in the local crate is
+/// verified as an entry function.
+#[proc_macro_attribute]
+pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return "compile_error!(\"`#[rbmc::proof]` does not take arguments\");".parse().unwrap();
+    }
+    let tool_attr: TokenStream = "#[rbmctool::proof]".parse().unwrap();
+    tool_attr.into_iter().chain(item).collect()
+}
+
+/// Set the loop bound of a function, or the default loop bound of a harness.
+/// The argument is an integer literal.
+#[proc_macro_attribute]
+pub fn unwind(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let bound = attr.to_string();
+    if bound.parse::<usize>().is_err() {
+        return "compile_error!(\"`#[rbmc::unwind]` takes an integer literal, e.g., `#[rbmc::unwind(8)]`\");"
+            .parse()
+            .unwrap();
+    }
+    let tool_attr: TokenStream = format!("#[rbmctool::unwind({bound})]").parse().unwrap();
+    tool_attr.into_iter().chain(item).collect()
+}
+
+/// Replace a function by the marked one. The argument is the path or the
+/// trimmed name of the replaced function.
+#[proc_macro_attribute]
+pub fn stub(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if attr.is_empty() {
+        return compile_error(
+            "`#[rbmc::stub]` takes a function, e.g., `#[rbmc::stub(Instant::now)]`",
+        );
+    }
+    let tool_attr: TokenStream = format!("#[rbmctool::stub({attr})]").parse().unwrap();
+    tool_attr.into_iter().chain(item).collect()
+}
+
+/// Precondition of a function. See `contract` for the generated functions.
+#[proc_macro_attribute]
+pub fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
+    contract(Clause::Requires, attr, item)
+}
+
+/// Postcondition of a function. The return value is `result`.
+#[proc_macro_attribute]
+pub fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
+    contract(Clause::Ensures, attr, item)
+}
+
+/// The places written by a function, e.g., `#[rbmc::modifies(*p, self.len)]`.
+#[proc_macro_attribute]
+pub fn modifies(attr: TokenStream, item: TokenStream) -> TokenStream {
+    contract(Clause::Modifies, attr, item)
+}
+
+/// Implement `rbmc::Arbitrary` for a struct or an enum. Its nondet values
+/// are built by `bmc-driver`, so that the fields are arbitrary as well.
+#[proc_macro_derive(Arbitrary)]
+pub fn derive_arbitrary(item: TokenStream) -> TokenStream {
+    let mut tokens = item.into_iter().skip_while(|t| {
+        !matches!(t, TokenTree::Ident(i) if ["struct", "enum", "union"].contains(&i.to_string().as_str()))
+    });
+    match tokens.next() {
+        Some(TokenTree::Ident(i)) if i.to_string() != "union" => {}
+        _ => return compile_error("`Arbitrary` can only be derived for structs and enums"),
+    }
+    let Some(TokenTree::Ident(name)) = tokens.next() else {
+        return compile_error("Invalid item");
+    };
+    if matches!(tokens.next(), Some(TokenTree::Punct(p)) if p.as_char() == '<') {
+        return compile_error("`Arbitrary` can not be derived for generic types");
+    }
+    format!("impl ::rbmc::Arbitrary for {name} {{}}").parse().unwrap()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Clause {
+    Requires,
+    Ensures,
+    Modifies,
+}
+
+fn compile_error(msg: &str) -> TokenStream {
+    format!("compile_error!({msg:?});").parse().unwrap()
+}
+
+/// The first contract attribute of a function collects the others, and
+/// generates two functions next to it. For `fn f(x: T) -> R`,
+///
+/// ```ignore
+/// fn __rbmc_check_f(x: T) -> R {
+///     rbmc::contract::requires(..);
+///     let result: R = f(x);
+///     rbmc::contract::ensures(..);
+///     result
+/// }
+///
+/// fn __rbmc_replace_f(x: T) -> R {
+///     rbmc::contract::requires(..);
+///     *p = rbmc::nondet(); // for each place in `modifies`
+///     let result: R = rbmc::nondet();
+///     rbmc::contract::ensures(..);
+///     result
+/// }
+/// ```
+///
+/// `bmc-driver` assumes `requires` and checks `ensures` in the first one, and
+/// does the opposite in the second one.
+fn contract(clause: Clause, attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut clauses = vec![(clause, attr)];
+    let mut attrs = Vec::new();
+    let mut tokens = item.into_iter().peekable();
+    while let Some(TokenTree::Punct(p)) = tokens.peek() {
+        if p.as_char() != '#' {
+            break;
+        }
+        let pound = tokens.next().unwrap();
+        let Some(TokenTree::Group(group)) = tokens.next() else {
+            return compile_error("Invalid attribute");
+        };
+        match contract_clause(&group) {
+            Some(clause) => clauses.push(clause),
+            None => attrs.extend([pound, TokenTree::Group(group)]),
+        }
+    }
+    let sig = tokens.collect::<Vec<_>>();
+
+    let Some(i) =
+        sig.iter().position(|t| matches!(t, TokenTree::Ident(i) if i.to_string() == "fn"))
+    else {
+        return compile_error("Contracts are only supported on functions");
+    };
+    let name = match sig.get(i + 1) {
+        Some(TokenTree::Ident(name)) => name.to_string(),
+        _ => return compile_error("Contracts are only supported on functions"),
+    };
+    let params = match sig.get(i + 2) {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => g.stream(),
+        _ => return compile_error("Contracts of generic functions are not supported"),
+    };
+    let body = match sig.last() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => g.clone(),
+        _ => return compile_error("Contracts are only supported on functions with bodies"),
+    };
+    let ret = sig[i + 3..sig.len() - 1].iter().cloned().collect::<TokenStream>();
+    let ret_ty = match ret.to_string().strip_prefix("->") {
+        Some(ty) => ty.to_string(),
+        None if ret.is_empty() => "()".to_string(),
+        None => return compile_error("`where` clauses are not supported by contracts"),
+    };
+
+    let mut args = Vec::new();
+    for param in split_commas(params.clone()) {
+        match param_name(&param) {
+            Some(arg) => args.push(arg),
+            None => return compile_error("Contracts need parameters named by identifiers"),
+        }
+    }
+    // Methods and functions mentioning `Self` are associated functions
+    let is_associated = args.first().is_some_and(|arg| arg == "self")
+        || params.to_string().contains("Self")
+        || ret_ty.contains("Self");
+    let callee = if is_associated { format!("Self::{name}") } else { name.clone() };
+
+    let mut requires = String::new();
+    let mut ensures = String::new();
+    let mut modifies = String::new();
+    for (clause, cond) in clauses {
+        match clause {
+            Clause::Requires => requires += &format!("::rbmc::contract::requires({cond});"),
+            Clause::Ensures => ensures += &format!("::rbmc::contract::ensures({cond});"),
+            Clause::Modifies => {
+                for place in split_commas(cond) {
+                    let place = place.into_iter().collect::<TokenStream>();
+                    modifies += &format!("{place} = ::rbmc::nondet();");
+                }
+            }
+        }
+    }
+    let nondet = if ret.is_empty() { "()" } else { "::rbmc::nondet()" };
+    let allow = "#[doc(hidden)] #[allow(dead_code, non_snake_case, unused_mut, unused_variables)]";
+    let wrappers = format!(
+        "{allow} fn __rbmc_check_{name}({params}) -> {ret_ty} {{
+            {requires}
+            let result: {ret_ty} = {callee}({args});
+            {ensures}
+            result
+        }}
+        {allow} fn __rbmc_replace_{name}({params}) -> {ret_ty} {{
+            {requires}
+            {modifies}
+            let result: {ret_ty} = {nondet};
+            {ensures}
+            result
+        }}",
+        args = args.join(", "),
+    );
+    let Ok(wrappers) = wrappers.parse::<TokenStream>() else {
+        return compile_error("Invalid contract");
+    };
+
+    let tool_attr: TokenStream = "#[rbmctool::contract]".parse().unwrap();
+    let sig = sig[..sig.len() - 1].iter().cloned();
+    tool_attr
+        .into_iter()
+        .chain(attrs)
+        .chain(sig)
+        .chain([TokenTree::Group(body)])
+        .chain(wrappers)
+        .collect()
+}
+
+/// The clause of `#[rbmc::requires(..)]`, `#[requires(..)]` and so on
+fn contract_clause(attr: &Group) -> Option<(Clause, TokenStream)> {
+    let tokens = attr.stream().into_iter().collect::<Vec<_>>();
+    let path = tokens[..tokens.len().saturating_sub(1)].iter().cloned().collect::<TokenStream>();
+    let clause = match path.to_string().replace(' ', "").as_str() {
+        "rbmc::requires" | "requires" => Clause::Requires,
+        "rbmc::ensures" | "ensures" => Clause::Ensures,
+        "rbmc::modifies" | "modifies" => Clause::Modifies,
+        _ => return None,
+    };
+    match tokens.last() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+            Some((clause, g.stream()))
+        }
+        _ => None,
+    }
+}
+
+/// Split by the commas out of angle brackets
+fn split_commas(tokens: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut items = vec![Vec::new()];
+    let mut depth = 0;
+    let mut is_arrow = false;
+    for token in tokens {
+        if let TokenTree::Punct(p) = &token {
+            match p.as_char() {
+                ',' if depth == 0 => {
+                    items.push(Vec::new());
+                    continue;
+                }
+                '<' => depth += 1,
+                '>' if !is_arrow => depth -= 1,
+                _ => {}
+            }
+            is_arrow = p.as_char() == '-' && p.spacing() == Spacing::Joint;
+        } else {
+            is_arrow = false;
+        }
+        items.last_mut().unwrap().push(token);
+    }
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+/// `x` in `x: T`, `mut x: T` and `self` in the receivers
+fn param_name(param: &[TokenTree]) -> Option<String> {
+    let idents = param
+        .iter()
+        .take_while(|t| !matches!(t, TokenTree::Punct(p) if p.as_char() == ':'))
+        .filter_map(|t| match t {
+            TokenTree::Ident(i) if i.to_string() == "mut" || i.to_string() == "ref" => None,
+            TokenTree::Ident(i) => Some(i.to_string()),
+            TokenTree::Punct(p) if p.as_char() == '&' => None,
+            // Lifetimes and patterns
+            _ => Some(String::new()),
+        })
+        .collect::<Vec<_>>();
+    match idents.as_slice() {
+        [name] if !name.is_empty() => Some(name.clone()),
+        _ => None,
+    }
+}
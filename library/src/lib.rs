@@ -4,3 +4,203 @@
 pub fn nondet<T>() -> T {
     panic!("Nondet is a place holder for RBMC")
 }
+
+/// Assume `cond`. Paths where it does not hold are not verified.
+///
+/// ```ignore
+/// let x: u32 = rbmc::nondet();
+/// rbmc::assume(x < 100);
+/// ```
+#[inline(never)]
+pub fn assume(_cond: bool) {}
+
+/// Types with well-formed nondet values. Compared with `nondet`, enums only
+/// take their variants, and a `Box` or a reference points to a fresh object
+/// with an arbitrary value. Recursive types are cut off at the first
+/// recursion, e.g., a list node has no successor.
+///
+/// ```ignore
+/// #[derive(rbmc::Arbitrary)]
+/// enum Shape {
+///     Circle(u32),
+///     Rect { w: u32, h: Box<u32> },
+/// }
+///
+/// let shape: Shape = rbmc::Arbitrary::any();
+/// ```
+pub trait Arbitrary: Sized {
+    /// The values are generated by RBMC. The body is never executed.
+    fn any() -> Self {
+        panic!("Arbitrary::any is a place holder for RBMC")
+    }
+}
+
+/// Derive `Arbitrary` for a struct or an enum, which is not generic.
+pub use rbmc_macros::Arbitrary;
+
+/// A nondet value in `lo..hi`. The range must not be empty.
+///
+/// ```ignore
+/// let i: usize = rbmc::nondet_range(0, 10);
+/// ```
+#[inline(never)]
+pub fn nondet_range<T: Arbitrary + PartialOrd>(_lo: T, _hi: T) -> T {
+    panic!("nondet_range is a place holder for RBMC")
+}
+
+/// An array of arbitrary elements
+#[inline(never)]
+pub fn nondet_array<T: Arbitrary, const N: usize>() -> [T; N] {
+    panic!("nondet_array is a place holder for RBMC")
+}
+
+/// A slice of at most `max_len` arbitrary elements. `max_len` must be a
+/// constant.
+#[inline(never)]
+pub fn nondet_slice<T: Arbitrary>(_max_len: usize) -> &'static [T] {
+    panic!("nondet_slice is a place holder for RBMC")
+}
+
+/// A vector of at most `max_len` arbitrary elements. `max_len` must be a
+/// constant.
+#[inline(never)]
+pub fn nondet_vec<T: Arbitrary>(_max_len: usize) -> Vec<T> {
+    panic!("nondet_vec is a place holder for RBMC")
+}
+
+macro_rules! arbitrary {
+    ($($ty:ty),*) => {
+        $(impl Arbitrary for $ty {})*
+    };
+}
+
+arbitrary!(bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, ());
+
+macro_rules! arbitrary_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: Arbitrary),*> Arbitrary for ($($name,)*) {}
+    };
+}
+
+arbitrary_tuple!(A);
+arbitrary_tuple!(A, B);
+arbitrary_tuple!(A, B, C);
+arbitrary_tuple!(A, B, C, D);
+arbitrary_tuple!(A, B, C, D, E);
+arbitrary_tuple!(A, B, C, D, E, F);
+
+impl<T: Arbitrary, const N: usize> Arbitrary for [T; N] {}
+
+impl<T: Arbitrary> Arbitrary for Option<T> {}
+
+impl<T: Arbitrary, E: Arbitrary> Arbitrary for Result<T, E> {}
+
+impl<T: Arbitrary> Arbitrary for Box<T> {}
+
+impl<T: Arbitrary> Arbitrary for &T {}
+
+impl<T: Arbitrary> Arbitrary for &mut T {}
+
+impl<T: Arbitrary> Arbitrary for &[T] {}
+
+impl<T: Arbitrary> Arbitrary for Vec<T> {}
+
+/// Check `cond`. The message is reported if it does not hold, and must be a
+/// string literal.
+///
+/// ```ignore
+/// rbmc::assert(x + 1 > x, "x + 1 is greater than x");
+/// ```
+#[inline(never)]
+pub fn assert(_cond: bool, _msg: &'static str) {}
+
+/// Check whether `cond` can hold here. The cover is reported as satisfied or
+/// unsatisfiable, and does not change the verdict. The message must be a
+/// string literal.
+///
+/// ```ignore
+/// rbmc::cover(x == 42, "x can be 42");
+/// ```
+#[inline(never)]
+pub fn cover(_cond: bool, _msg: &'static str) {}
+
+/// Proof harness. For example,
+///
+/// ```ignore
+/// #[rbmc::proof]
+/// fn check_add() {
+///     let x: u8 = rbmc::nondet();
+///     let _ = x / 2 + 100;
+/// }
+/// ```
+pub use rbmc_macros::proof;
+
+/// Loop bound. On a harness, it is the default bound of all loops reached
+/// by the harness. On other functions, it only bounds the loops in them.
+///
+/// ```ignore
+/// #[rbmc::proof]
+/// #[rbmc::unwind(8)]
+/// fn check_sum() {
+///     let n: usize = rbmc::nondet();
+///     let mut s = 0;
+///     for i in 0..n.min(5) {
+///         s += i;
+///     }
+/// }
+/// ```
+pub use rbmc_macros::unwind;
+
+/// Stub. Calls to the function in the argument go to the marked function,
+/// which has the same signature. It models code that is hard to verify,
+/// e.g., I/O. Stubs can also be given by `--stub ORIGINAL=REPLACEMENT`.
+///
+/// ```ignore
+/// #[rbmc::stub(std::process::id)]
+/// fn fake_id() -> u32 {
+///     rbmc::nondet()
+/// }
+/// ```
+pub use rbmc_macros::stub;
+
+/// Loop invariant. RBMC checks it when the loop is entered and after an
+/// arbitrary iteration instead of unwinding the loop. Call it at the head of
+/// the loop, before any exit, so that the state after the loop satisfies it.
+///
+/// ```ignore
+/// let n: u32 = rbmc::nondet();
+/// let mut i = 0;
+/// loop {
+///     rbmc::loop_invariant(i <= n);
+///     if i == n { break; }
+///     i += 1;
+/// }
+/// ```
+#[inline(never)]
+pub fn loop_invariant(_cond: bool) {}
+
+/// Function contract. A function is checked against its contract by
+/// `--check-contracts`, and its calls are replaced by the contract with
+/// `--replace-contracts`: the precondition is checked, the places in
+/// `modifies` are havocked and the postcondition is assumed.
+///
+/// ```ignore
+/// #[rbmc::requires(*p < 100)]
+/// #[rbmc::ensures(*p <= 100 && result == *p)]
+/// #[rbmc::modifies(*p)]
+/// fn inc(p: &mut u32) -> u32 {
+///     *p += 1;
+///     *p
+/// }
+/// ```
+pub use rbmc_macros::{ensures, modifies, requires};
+
+/// Markers of contract clauses in the functions generated by contracts
+#[doc(hidden)]
+pub mod contract {
+    #[inline(never)]
+    pub fn requires(_cond: bool) {}
+
+    #[inline(never)]
+    pub fn ensures(_cond: bool) {}
+}
@@ -1,62 +1,174 @@
-// #![feature(rustc_private)]
-
-// extern crate rustc_driver;
-// extern crate rustc_interface;
-// extern crate rustc_middle;
-// extern crate rustc_smir;
-// extern crate stable_mir;
-
-// use std::process::Command;
-
-// fn parse_mirv_flags() -> String {
-//     let mut args = std::env::args().into_iter().collect::<Vec<_>>();
-//     let idx =
-//         args.iter().enumerate().find_map(|(i, x)| if x == "--rbmc-args" { Some(i) } else { None });
-//     match idx {
-//         Some(i) => args.split_off(i).join(" "),
-//         None => "".to_string(),
-//     }
-// }
-
-// fn main() {
-//     let metadata_cmd = cargo_metadata::MetadataCommand::new();
-//     let metadata = match metadata_cmd.exec() {
-//         Ok(m) => m,
-//         _ => panic!("Fail to get the metadata of the current project"),
-//     };
-
-//     // We only check the current crate
-//     if let Some(root) = metadata.root_package() {
-//         for target in &root.targets {
-//             if target.is_test() {
-//                 continue;
-//             }
-//             println!("Target: {}", target.name);
-//             let mut cmd = Command::new("cargo");
-//             cmd
-//                 // Set the crate being verified
-//                 .env(cli::RBMC_CRATE, target.name.as_str())
-//                 // Mirv arguments
-//                 .env(cli::RBMC_FLAGS, parse_mirv_flags())
-//                 // Wrap the rustc with rbmc
-//                 .env("RUSTC_WRAPPER", "rbmc")
-//                 // No need to compile the whole project
-//                 .arg("build");
-
-//             let exit_status =
-//                 cmd.spawn().expect("could not run cargo").wait().expect("failed to wait for cargo");
-
-//             if !exit_status.success() {
-//                 std::process::exit(exit_status.code().unwrap_or(-1))
-//             }
-//         }
-//     } else {
-//         panic!("Not support lib yet");
-//     }
-// }
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
 
-/// Cargo rbmc
+use cargo_metadata::camino::Utf8Path;
+use cargo_metadata::{MetadataCommand, Package, Target};
+
+/// Arguments after `--rbmc-args` are passed to `bmc-driver`, separated by
+/// `RBMC_FLAGS_SEP` such that arguments may contain spaces.
+fn rbmc_flags() -> String {
+    let mut args = std::env::args().collect::<Vec<_>>();
+    match args.iter().position(|arg| arg == "--rbmc-args") {
+        Some(i) => args.split_off(i + 1).join(&rust_bmc::RBMC_FLAGS_SEP.to_string()),
+        None => String::new(),
+    }
+}
 
+/// The host triple. Local packages are compiled for it explicitly such that
+/// build scripts and proc-macros are compiled by the real rustc.
+fn host_triple() -> String {
+    let output = Command::new("rustc").arg("-vV").output().expect("Fail to run rustc");
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .find_map(|line| line.strip_prefix("host: ").map(String::from))
+        .expect("Fail to get the host triple")
+}
+
+/// Tests, benches, examples, build scripts and proc-macros are not verified
+fn is_verifiable(target: &Target) -> bool {
+    !(target.is_test()
+        || target.is_bench()
+        || target.is_example()
+        || target.is_custom_build()
+        || target.is_proc_macro())
+}
+
+fn cargo(subcommand: &str, package: &Package, target_dir: &Utf8Path, host: &str) -> Command {
+    let mut cmd = Command::new("cargo");
+    // Build scripts and proc-macros must be compiled by the toolchain of `bmc-driver`
+    if !rust_bmc::is_dev_home() {
+        cmd.env("RUSTUP_TOOLCHAIN", rust_bmc::rust_toolchain());
+    }
+    cmd.env("PATH", rust_bmc::path())
+        .arg(subcommand)
+        .arg("-p")
+        .arg(format!("{}@{}", package.name, package.version))
+        .args(["--target", host])
+        .arg("--target-dir")
+        .arg(target_dir);
+    cmd
+}
+
+/// Run `cargo check` with `rbmc` as the rustc wrapper and return the verdict
+fn verify(package: &Package, target: &Target, target_dir: &Utf8Path, host: &str) -> String {
+    let mut cmd = cargo("check", package, target_dir, host);
+    if target.is_bin() {
+        cmd.args(["--bin", &target.name]);
+    } else {
+        cmd.arg("--lib");
+    }
+    cmd
+        // Set the crate being verified
+        .env(rust_bmc::RBMC_CRATE, target.name.replace('-', "_"))
+        // RBMC arguments
+        .env(rust_bmc::RBMC_FLAGS, rbmc_flags())
+        // Wrap the rustc with rbmc
+        .env("RUSTC_WRAPPER", "rbmc")
+        .stdout(Stdio::piped());
+
+    let mut child = cmd.spawn().expect("Fail to run cargo");
+    let mut verdict = None;
+    let mut unsupported = false;
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let line = line.unwrap();
+        println!("{line}");
+        if let Some(res) = line.strip_prefix("Verification result: ") {
+            verdict = Some(res.trim_end_matches('.').to_string());
+        } else if line == "Unsupported features:" {
+            unsupported = true;
+        } else if line.starts_with('{') {
+            // The document of `--output-format json`
+            if let Ok(report) = serde_json::from_str::<serde_json::Value>(&line) {
+                verdict = report["result"].as_str().map(String::from);
+                unsupported |= report["harnesses"].as_array().is_some_and(|harnesses| {
+                    harnesses.iter().any(|h| h.get("unsupported").is_some())
+                });
+            }
+        }
+    }
+    let status = child.wait().expect("Fail to wait for cargo");
+    match verdict {
+        // Unknown because of unsupported features
+        Some(res) if status.success() && res == "unknown" && unsupported => {
+            "unknown (unsupported)".to_string()
+        }
+        Some(res) if status.success() => res,
+        _ => "error".to_string(),
+    }
+}
+
+/// The exit code of a verdict. Verdicts unknown to `cargo rbmc` are errors.
+fn exit_code(verdict: &str) -> i32 {
+    match verdict {
+        "success" | "skipped" => rust_bmc::exit_code::SUCCESS,
+        "fail" => rust_bmc::exit_code::FAIL,
+        "unknown" => rust_bmc::exit_code::UNKNOWN,
+        "unknown (unsupported)" => rust_bmc::exit_code::UNSUPPORTED,
+        _ => rust_bmc::exit_code::ERROR,
+    }
+}
+
+/// A violated property is the most important to report, then errors that
+/// prevent verification.
+fn severity(code: i32) -> u8 {
+    match code {
+        rust_bmc::exit_code::FAIL => 4,
+        rust_bmc::exit_code::ERROR => 3,
+        rust_bmc::exit_code::UNSUPPORTED => 2,
+        rust_bmc::exit_code::UNKNOWN => 1,
+        _ => 0,
+    }
+}
+
+/// Cargo rbmc
+///
+/// Verify the lib and bin targets of the local packages. Run in the root of a package
+/// to verify it, or in the root of a workspace to verify the default members.
 fn main() {
-    println!("TODO");
+    rust_bmc::setup_toolchain();
+
+    let metadata =
+        MetadataCommand::new().exec().expect("Fail to get the metadata of the current project");
+    let packages = match metadata.root_package() {
+        Some(root) => vec![root],
+        None => metadata.workspace_default_packages(),
+    };
+    // Keep the artifacts of rbmc away from the normal ones
+    let target_dir = metadata.target_directory.join("rbmc");
+    let host = host_triple();
+
+    let mut verdicts = Vec::new();
+    for package in packages {
+        // Make all targets of the package be compiled by `bmc-driver` again
+        let status = cargo("clean", package, &target_dir, &host)
+            .arg("--quiet")
+            .status()
+            .expect("Fail to run cargo clean");
+        if !status.success() {
+            eprintln!("error: Fail to clean the package {}", package.name);
+            std::process::exit(rust_bmc::exit_code::ERROR);
+        }
+
+        // The lib is verified first, such that it is not verified again as
+        // a dependency of bins.
+        let mut targets = package.targets.iter().filter(|t| is_verifiable(t)).collect::<Vec<_>>();
+        targets.sort_by_key(|t| t.is_bin());
+        for target in targets {
+            println!("Target: {} ({})", target.name, package.name);
+            let verdict = verify(package, target, &target_dir, &host);
+            verdicts.push((package.name.clone(), target.name.clone(), verdict));
+        }
+    }
+
+    println!("\nSummary:");
+    for (package, target, verdict) in verdicts.iter() {
+        println!("{package}::{target}: {verdict}");
+    }
+    let code = verdicts
+        .iter()
+        .map(|(_, _, verdict)| exit_code(verdict))
+        .max_by_key(|code| severity(*code))
+        .unwrap_or(rust_bmc::exit_code::SUCCESS);
+    std::process::exit(code);
 }
@@ -3,11 +3,28 @@ use std::process::Command;
 /// Set flags and run bmc-driver
 fn main() {
     rust_bmc::setup_toolchain();
+    let args = rust_bmc::rbmc_args();
+    if rust_bmc::is_rustc_wrapper() {
+        let status = if rust_bmc::is_passthrough(&args) {
+            let rustc = std::env::args().nth(1).expect("No rustc");
+            Command::new(rustc).args(args).status()
+        } else {
+            Command::new("bmc-driver")
+                .env("PATH", rust_bmc::path())
+                .env("RUSTC_ARGS", rust_bmc::rustc_args())
+                .args(args)
+                .status()
+        }
+        .expect("Fail to run rustc");
+        std::process::exit(status.code().unwrap_or(rust_bmc::exit_code::ERROR));
+    }
+
     let status = Command::new("bmc-driver")
         .env("PATH", rust_bmc::path())
         .env("RUSTC_ARGS", rust_bmc::rustc_args())
-        .args(rust_bmc::rbmc_args())
+        .args(args)
         .status()
         .expect("Fail to run RBMC");
-    assert!(status.success());
+    // Killed by signals
+    std::process::exit(status.code().unwrap_or(rust_bmc::exit_code::ERROR));
 }
@@ -9,6 +9,27 @@ use std::path::PathBuf;
 
 const VERSION: &str = std::env!("CARGO_PKG_VERSION");
 
+/// The crate verified by `cargo rbmc`
+pub const RBMC_CRATE: &str = "RBMC_CRATE";
+/// Arguments of `bmc-driver` passed by `cargo rbmc`
+pub const RBMC_FLAGS: &str = "RBMC_FLAGS";
+/// The separator of the arguments in `RBMC_FLAGS`, as `CARGO_ENCODED_RUSTFLAGS` does
+pub const RBMC_FLAGS_SEP: char = '\x1f';
+
+/// Exit codes of `bmc-driver`, `rbmc` and `cargo rbmc`
+pub mod exit_code {
+    /// All properties hold, or nothing is verified
+    pub const SUCCESS: i32 = 0;
+    /// Some property is violated
+    pub const FAIL: i32 = 10;
+    /// The solver can not decide, e.g., timeout
+    pub const UNKNOWN: i32 = 11;
+    /// Unsupported features are reached
+    pub const UNSUPPORTED: i32 = 12;
+    /// Compilation errors or internal errors of RBMC
+    pub const ERROR: i32 = 13;
+}
+
 /// Export bin folder in path
 pub fn path() -> String {
     let rbmc_bin = &[rbmc_bin()];
@@ -45,8 +66,23 @@ pub fn rbmc_lib() -> PathBuf {
     rbmc_home().join("lib")
 }
 
+/// `cargo rbmc` runs `rbmc` as `RUSTC_WRAPPER`
+pub fn is_rustc_wrapper() -> bool {
+    std::env::var_os(RBMC_CRATE).is_some()
+}
+
 pub fn rbmc_args() -> Vec<String> {
-    std::env::args().skip(1).into_iter().collect()
+    // As `RUSTC_WRAPPER`, `rbmc` is invoked by `rbmc <rustc> <args>`
+    let n = if is_rustc_wrapper() { 2 } else { 1 };
+    std::env::args().skip(n).collect()
+}
+
+/// `cargo rbmc` compiles packages for an explicit target. Thus, build scripts,
+/// proc-macros and their dependencies are compiled without `--target`. They and
+/// the queries (`--print`) of `cargo` are passed to the real `rustc`.
+pub fn is_passthrough(args: &[String]) -> bool {
+    !args.iter().any(|arg| arg == "--target" || arg.starts_with("--target="))
+        || args.iter().any(|arg| arg.starts_with("--print"))
 }
 
 pub fn rustc_args() -> String {
@@ -66,6 +102,9 @@ pub fn rustc_args() -> String {
         // Use opt-level=1 to close all runtime checkings, which will be generated by RBMC.
         "-C",
         "opt-level=1",
+        // Except overflow checks, which tell checked arithmetic from wrapping one
+        "-C",
+        "overflow-checks=on",
         // Only abort
         "-C",
         "panic=abort",
@@ -75,19 +114,32 @@ pub fn rustc_args() -> String {
         "always-encode-mir",
         "-Z",
         "mir-enable-passes=+ReorderBasicBlocks",
+        // Register the tool of attributes from `rbmc`, e.g., `#[rbmc::proof]`
+        "-Z",
+        "crate-attr=feature(register_tool)",
+        "-Z",
+        "crate-attr=register_tool(rbmctool)",
     ]
     .join(" ")
 }
 
-fn toolchain_lib() -> PathBuf {
+/// The toolchain `RBMC` is installed with
+pub fn rust_toolchain() -> String {
     let rust_toolchain_file = rbmc_home().join("rust-toolchain");
-    let rust_toolchain =
-        std::fs::read_to_string(rust_toolchain_file).expect("Invalid rust-toolchain file");
-    home::rustup_home().unwrap().join("toolchains").join(rust_toolchain).join("lib")
+    std::fs::read_to_string(rust_toolchain_file).expect("Invalid rust-toolchain file")
+}
+
+/// Whether `RBMC` runs from the repository
+pub fn is_dev_home() -> bool {
+    rbmc_home().starts_with(std::env!("REPO_ROOT"))
+}
+
+fn toolchain_lib() -> PathBuf {
+    home::rustup_home().unwrap().join("toolchains").join(rust_toolchain()).join("lib")
 }
 
 pub fn setup_toolchain() {
-    if rbmc_home().starts_with(std::env!("REPO_ROOT")) {
+    if is_dev_home() {
         return;
     }
 
@@ -130,6 +130,8 @@ fn build_libs() {
     }
     // Copy std libraries
     copy_std_lib(&artifacts);
+    // Copy proc-macros of `rbmc`
+    copy_rbmc_macros(&artifacts);
 }
 
 fn is_rust_lib(artifact: &Artifact) -> bool {
@@ -170,6 +172,20 @@ fn copy_std_lib(artifacts: &[Artifact]) {
     });
 }
 
+fn copy_rbmc_macros(artifacts: &[Artifact]) {
+    let lib_path = build_root().join("lib");
+    artifacts
+        .iter()
+        .filter(|&artifact| is_rbmc_lib(artifact) && artifact.target.is_proc_macro())
+        .for_each(|artifact| {
+            artifact
+                .filenames
+                .iter()
+                .filter(|&path| path.extension() == Some("so"))
+                .for_each(|lib| cp(lib.as_std_path(), lib_path.as_path()))
+        });
+}
+
 fn install() {
     let build_root = build_root();
     assert!(build_root.exists());